@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day01, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day01>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -30,6 +30,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day01);
     const SAMPLE1: &str = r#"(())"#;
     const SAMPLE2: &str = "))(((((";
     #[test]
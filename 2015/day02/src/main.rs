@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day02, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day02>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -43,6 +43,7 @@ fn ribbon_needed(l: usize, w: usize, h: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day02);
     #[test]
     fn p1_test() {
         assert_eq!(part1("2x3x4").unwrap(), 58);
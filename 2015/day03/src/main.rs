@@ -2,10 +2,10 @@ use std::collections::HashSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day03, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day03>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -55,6 +55,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day03);
     #[test]
     fn p1_test() {
         assert_eq!(part1(">").unwrap(), 2);
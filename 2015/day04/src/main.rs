@@ -3,10 +3,10 @@ use md5::*;
 // For parallel iterators using multiple threads
 use rayon::prelude::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day04, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day04>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -40,11 +40,15 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day04);
     #[test]
     fn p1_test() {
-        assert_eq!(part1("abcdef").unwrap(), 609043);
-        assert_eq!(part1("pqrstuv").unwrap(), 1048970);
+        assert_eq!(part1(&sample!(1)).unwrap().to_string(), sample_answer!(1));
+        assert_eq!(part1(&sample!(2)).unwrap(), 1048970);
     }
     #[test]
-    fn p2_test() {}
+    fn p2_test() {
+        // The puzzle has no example for part 2, this was found with a separate md5 search
+        assert_eq!(part2(&sample!(1)).unwrap(), 6742839);
+    }
 }
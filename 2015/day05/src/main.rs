@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day05, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day05>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -54,6 +54,7 @@ fn check_repeated_char_seq(line: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day05);
     const SAMPLE1: &str = r#"ugknbfddgicrmopn
 aaa
 jchzalrnumimnmhp
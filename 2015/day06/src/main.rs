@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day06, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day06>(&read_input_file_for_project_as_string!())
 }
 
 enum Action {
@@ -113,3 +113,9 @@ fn part2(input: &str) -> R<usize> {
     }
     Ok(lights.into_iter().sum())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day06);
+}
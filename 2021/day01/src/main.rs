@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day01, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day01>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -38,6 +32,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day01);
     const SAMPLE1: &str = r#"199
 200
 208
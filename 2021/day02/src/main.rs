@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day02, isize, isize);
+
+fn main() -> R<()> {
+    run::<Day02>(&read_input_file_for_project_as_string!())
 }
 #[derive(Default)]
 struct Position {
@@ -56,6 +50,7 @@ fn part2(input: &str) -> anyhow::Result<isize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day02);
     const SAMPLE1: &str = r#"forward 5
 down 5
 forward 8
@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day03, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day03>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -84,6 +78,7 @@ fn find_number(numbers: Vec<&str>, most_common: bool, index: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day03);
     const SAMPLE1: &str = r#"00100
 11110
 10110
@@ -2,16 +2,10 @@ use std::ops::Not;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day04, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day04>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Default, Debug)]
@@ -107,6 +101,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day04);
     const SAMPLE1: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
 22 13 17 11  0
@@ -1,32 +1,18 @@
 use std::collections::HashMap;
 
 use common::{
-    winnow::{
-        ascii::digit1,
-        combinator::{separated_pair},
-        PResult, Parser,
-    },
+    winnow::{combinator::separated_pair, PResult, Parser},
     *,
 };
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
+solution!(Day05, usize, usize);
 
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
-struct Point {
-    x: usize,
-    y: usize,
+fn main() -> R<()> {
+    run::<Day05>(&read_input_file_for_project_as_string!())
 }
 
+type Point = Point2<usize>;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct LineSegment {
     start: Point,
@@ -43,21 +29,12 @@ impl LineSegment {
 }
 
 fn line_segment(input: &mut &str) -> PResult<LineSegment> {
-    let (start, _, end) = (point, " -> ", point).parse_next(input)?;
+    let (start, end) = separated_pair(point2, " -> ", point2).parse_next(input)?;
     Ok(LineSegment { start, end })
 }
 
-fn point(input: &mut &str) -> PResult<Point> {
-    let (x, y) = separated_pair(digit1.try_map(str::parse), ',', digit1.try_map(str::parse)).parse_next(input)?;
-    Ok(Point { x, y })
-}
-
 fn part1(input: &str) -> anyhow::Result<usize> {
-    let mut line_segments = vec![];
-    for mut line in input.lines() {
-        let a = line_segment.parse_next(&mut line).map_err(|e| anyhow::anyhow!(e))?;
-        line_segments.push(a);
-    }
+    let mut line_segments = parse_lines(line_segment, input)?;
     line_segments.retain(|l| l.is_horizontal() || l.is_vertical());
     let mut marked_count = HashMap::new();
     for line in line_segments {
@@ -76,11 +53,7 @@ fn part1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let mut line_segments = vec![];
-    for mut line in input.lines() {
-        let a = line_segment.parse_next(&mut line).map_err(|e| anyhow::anyhow!(e))?;
-        line_segments.push(a);
-    }
+    let line_segments = parse_lines(line_segment, input)?;
     let mut marked_count = HashMap::new();
     for line in line_segments {
         if line.is_horizontal() || line.is_vertical() {
@@ -126,6 +99,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day05);
     const SAMPLE1: &str = r#"0,9 -> 5,9
 8,0 -> 0,8
 9,4 -> 3,4
@@ -147,7 +121,7 @@ mod tests {
     #[test]
     fn point_winnow() {
         let mut a = "0,9";
-        let a = point.parse_next(&mut a).unwrap();
+        let a = point2.parse_next(&mut a).unwrap();
         assert_eq!(a, (Point { x: 0, y: 9 }));
     }
     #[test]
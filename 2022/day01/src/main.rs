@@ -1,28 +1,58 @@
-use common::read_input_file_for_project;
-fn main() {
-    let mut lines = read_input_file_for_project!();
+use common::*;
 
-    // Variables to store results
-    let mut biggest_sum = 0u64;
-    let mut current_sum = 0u64;
-    let mut sums: Vec<u64> = vec![];
-    while let Some(Ok(line)) = lines.next() {
-        // If the line is empty, we finished this entry so save the values
-        if line.is_empty() {
-            if current_sum > biggest_sum {
-                biggest_sum = current_sum
+struct Day01;
+
+impl Solution for Day01 {
+    /// Calories carried by each elf
+    type Input<'a> = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        let mut sums = vec![];
+        let mut current_sum = 0u64;
+        // End with an empty line so the last entry is saved too
+        for line in input.lines().chain([""]) {
+            // If the line is empty, we finished this entry so save the values
+            if line.is_empty() {
+                sums.push(current_sum);
+                current_sum = 0;
+            } else {
+                // Otherwise add to the current sum
+                current_sum += line.parse::<u64>()?;
             }
-            // For part 2 just save all the sums
-            sums.push(current_sum);
-            current_sum = 0;
-        } else {
-            // Otherwise add to the current sum
-            current_sum += line.parse::<u64>().expect("Failed to parse u64");
         }
+        Ok(sums)
     }
+
     // Part 1 just needs the biggest sum
-    println!("Part 1: {} ", biggest_sum);
-    // Part 2 needs the 3 biggest sums, so sort the sums in descending order and print.
-    sums.sort_by(|a, b| b.cmp(a));
-    println!("Part 2: {} ", sums[0] + sums[1] + sums[2]);
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        Ok(input.iter().copied().max().unwrap_or_default())
+    }
+
+    // Part 2 needs the 3 biggest sums, so sort the sums in descending order
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        let mut sums = input.clone();
+        sums.sort_by(|a, b| b.cmp(a));
+        Ok(sums.iter().take(3).sum())
+    }
+}
+
+fn main() -> R<()> {
+    run::<Day01>(&read_input_file_for_project_as_string!())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day01);
+    const SAMPLE1: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+    #[test]
+    fn p1_test() {
+        assert_eq!(Day01::solve_part1(SAMPLE1).unwrap(), 24000);
+    }
+    #[test]
+    fn p2_test() {
+        assert_eq!(Day01::solve_part2(SAMPLE1).unwrap(), 45000);
+    }
 }
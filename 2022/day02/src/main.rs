@@ -1,20 +1,31 @@
-use common::read_input_file_for_project;
-fn main() {
-    let mut lines = read_input_file_for_project!();
+use common::*;
+
+solution!(Day02, u64, u64);
+
+fn main() -> R<()> {
+    run::<Day02>(&read_input_file_for_project_as_string!())
+}
+
+fn part1(input: &str) -> R<u64> {
     let mut score = 0u64;
-    let mut score2 = 0u64;
-    while let Some(Ok(line)) = lines.next() {
+    for line in input.lines() {
         let opponent: RPS = (&line[..1]).into();
         let me: RPS = (&line[2..3]).into();
         let result = play_rps(&me, &opponent);
         score += result.to_score() + me.to_score();
+    }
+    Ok(score)
+}
 
+fn part2(input: &str) -> R<u64> {
+    let mut score2 = 0u64;
+    for line in input.lines() {
+        let opponent: RPS = (&line[..1]).into();
         let desired_result: RPSResult = (&line[2..3]).into();
         let my_move = determine_my_move(&opponent, &desired_result);
         score2 += desired_result.to_score() + my_move.to_score();
     }
-    println!("Part1 {}", score);
-    println!("Part2 {}", score2);
+    Ok(score2)
 }
 
 // Converts &str to RPS
@@ -92,3 +103,18 @@ enum RPS {
     Paper,
     Scissors,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day02);
+    const SAMPLE1: &str = "A Y\nB X\nC Z";
+    #[test]
+    fn p1_test() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 15);
+    }
+    #[test]
+    fn p2_test() {
+        assert_eq!(part2(SAMPLE1).unwrap(), 12);
+    }
+}
@@ -1,15 +1,16 @@
-use common::read_input_file_for_project;
-fn main() {
-    part1();
-    part2();
+use common::*;
+
+solution!(Day03, u64, u64);
+
+fn main() -> R<()> {
+    run::<Day03>(&read_input_file_for_project_as_string!())
 }
 
 /// Part 1 was simple, take each line and cut it in half.
 /// Iterator the characters in both havles and find the matching character and add it to the score
-fn part1() {
-    let mut lines = read_input_file_for_project!();
+fn part1(input: &str) -> R<u64> {
     let mut part1 = 0u64;
-    while let Some(Ok(line)) = lines.next() {
+    for line in input.lines() {
         let first = &line[..line.len() / 2];
         let second = &line[line.len() / 2..];
         let a: char = {
@@ -26,19 +27,19 @@ fn part1() {
         };
         part1 += char_to_score(a);
     }
-    println!("{}", part1);
+    Ok(part1)
 }
 
 /// Part took a bit longer since I needed to find a way to get 3 lines at the same time.
 /// I found Vec.chunks(N) which returns essentially an array of the next N items in the Vec.
 /// From there I just had to find 3 matching chars in each array
-fn part2() {
+fn part2(input: &str) -> R<u64> {
     let mut part2 = 0u64;
-    let lines2: Vec<_> = read_input_file_for_project!().collect();
+    let lines2: Vec<_> = input.lines().collect();
     for chunk in lines2.chunks(3) {
-        'outer2: for a in chunk[0].as_ref().unwrap().chars() {
-            for b in chunk[1].as_ref().unwrap().chars() {
-                for c in chunk[2].as_ref().unwrap().chars() {
+        'outer2: for a in chunk[0].chars() {
+            for b in chunk[1].chars() {
+                for c in chunk[2].chars() {
                     if a == b && a == c {
                         part2 += char_to_score(a);
                         break 'outer2;
@@ -47,7 +48,7 @@ fn part2() {
             }
         }
     }
-    println!("{}", part2);
+    Ok(part2)
 }
 
 /// Converts a char to a score
@@ -61,3 +62,23 @@ fn char_to_score(c: char) -> u64 {
         ascii - 96
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day03);
+    const SAMPLE1: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"#;
+    #[test]
+    fn p1_test() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 157);
+    }
+    #[test]
+    fn p2_test() {
+        assert_eq!(part2(SAMPLE1).unwrap(), 70);
+    }
+}
@@ -1,32 +1,51 @@
-use common::read_input_file_for_project;
-fn main() {
-    part1_and2();
+use common::*;
+
+solution!(Day04, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day04>(&read_input_file_for_project_as_string!())
+}
+
+fn part1(input: &str) -> R<usize> {
+    Ok(parse_pairs(input)
+        .filter(|(first, second)| {
+            (first.0 >= second.0 && first.1 <= second.1) || (second.0 >= first.0 && second.1 <= first.1)
+        })
+        .count())
+}
+
+fn part2(input: &str) -> R<usize> {
+    Ok(parse_pairs(input)
+        .filter(|(first, second)| !(first.1 < second.0 || second.1 < first.0))
+        .count())
 }
 
-fn part1_and2() {
-    let mut lines = read_input_file_for_project!();
-    let mut part1 = 0usize;
-    let mut part2 = 0usize;
-    while let Some(Ok(line)) = lines.next() {
+fn parse_pairs(input: &str) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + '_ {
+    input.lines().map(|line| {
         let ranges = {
             let mut split = line.split(',');
             (split.next().unwrap(), split.last().unwrap())
         };
-        let first = parse_range(ranges.0);
-        let second = parse_range(ranges.1);
-        if (first.0 >= second.0 && first.1 <= second.1) || (second.0 >= first.0 && second.1 <= first.1) {
-            part1 += 1;
-        }
-
-        if !(first.1 < second.0 || second.1 < first.0) {
-            part2 += 1;
-        }
-    }
-    println!("{}", part1);
-    println!("{}", part2);
+        (parse_range(ranges.0), parse_range(ranges.1))
+    })
 }
 
 fn parse_range(split: &str) -> (usize, usize) {
     let mut iter = split.split('-').map(|x| x.parse::<usize>().unwrap());
     (iter.next().unwrap(), iter.last().unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day04);
+    const SAMPLE1: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+    #[test]
+    fn p1_test() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 2);
+    }
+    #[test]
+    fn p2_test() {
+        assert_eq!(part2(SAMPLE1).unwrap(), 4);
+    }
+}
@@ -1,10 +1,10 @@
-use common::{read_input_file_for_project_as_string, R};
+use common::{read_input_file_for_project_as_string, run, solution, R};
 use std::{collections::VecDeque, str::Lines, vec};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {:#?}", part1(&input));
-    println!("Part2: {:#?}", part2(&input));
+solution!(Day05, String, String);
+
+fn main() -> R<()> {
+    run::<Day05>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<String> {
@@ -110,3 +110,10 @@ move 1 from 1 to 2"#;
         assert_eq!(part2(SAMPLE).unwrap(), "MCD")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer_tests;
+    answer_tests!(Day05);
+}
@@ -1,14 +1,14 @@
 use common::AdventOfCodeError;
-use common::{read_input_file_for_project_as_string, R};
+use common::{read_input_file_for_project_as_string, run, solution, R};
 use itertools::enumerate;
 use itertools::peek_nth;
 use itertools::Itertools;
 use std::vec;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {:#?}", part1(&input).unwrap());
-    println!("Part2: {:#?}", part2(&input).unwrap());
+solution!(Day06, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day06>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -64,3 +64,10 @@ mod day6_tests {
         assert_eq!(part2(SAMPLE5).unwrap(), 26);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer_tests;
+    answer_tests!(Day06);
+}
@@ -1,12 +1,12 @@
-use common::{read_input_file_for_project_as_string, R};
+use common::{read_input_file_for_project_as_string, run, solution, R};
 use slab_tree::*;
 
 use std::collections::{HashMap, VecDeque};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {:#?}", part1(&input).unwrap());
-    println!("Part2: {:#?}", part2(&input).unwrap());
+solution!(Day07, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day07>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug)]
@@ -155,3 +155,10 @@ $ ls
         assert_eq!(part2(SAMPLE1).unwrap(), 24933642);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer_tests;
+    answer_tests!(Day07);
+}
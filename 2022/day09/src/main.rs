@@ -1,11 +1,11 @@
 use std::collections::HashSet;
 
-use common::{read_input_file_for_project_as_string, R};
+use common::{read_input_file_for_project_as_string, run, solution, R};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {:#?}", part1(&input).unwrap());
-    println!("Part2: {:#?}", part2(&input).unwrap());
+solution!(Day09, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day09>(&read_input_file_for_project_as_string!())
 }
 
 fn calculate_tail_movement(head: &(isize, isize), tail: &(isize, isize)) -> (isize, isize) {
@@ -138,3 +138,10 @@ U 20"#;
         assert_eq!(part2(SAMPLE2).unwrap(), 36);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::answer_tests;
+    answer_tests!(Day09);
+}
@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {:#?}", part1(&input).unwrap());
-    println!("Part2: \n{}", part2(&input).unwrap());
+solution!(Day10, isize, String);
+
+fn main() -> R<()> {
+    run::<Day10>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<isize> {
@@ -36,8 +36,12 @@ fn part1(input: &str) -> R<isize> {
 }
 
 fn part2(input: &str) -> R<String> {
-    // CRT solution, transform to string at end
-    let solution = &mut [false; 240];
+    read_letters(&draw_screen(input)?, 40)
+}
+
+fn draw_screen(input: &str) -> R<[bool; 240]> {
+    // CRT solution, lit pixels are read as letters at the end
+    let mut solution = [false; 240];
     let mut cycle = 0usize;
     let mut sprite_pos = 1isize;
     for line in input.lines() {
@@ -61,23 +65,7 @@ fn part2(input: &str) -> R<String> {
             cycle += 1;
         }
     }
-    // Convert array into string
-    Ok(solution
-        .iter_mut()
-        .enumerate()
-        .map(|(i, x)| {
-            let mut str = "".to_owned();
-            if *x {
-                str += "#";
-            } else {
-                str += ".";
-            }
-            if i % 40 == 39 {
-                str += "\n";
-            }
-            str
-        })
-        .collect())
+    Ok(solution)
 }
 
 #[cfg(test)]
@@ -235,8 +223,14 @@ noop"#;
     }
     #[test]
     fn p2_test() {
+        // The sample draws stripes rather than letters
+        let screen = draw_screen(SAMPLE1).unwrap();
+        let screen = screen
+            .chunks(40)
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect::<String>() + "\n")
+            .collect::<String>();
         assert_eq!(
-            part2(SAMPLE1).unwrap(),
+            screen,
             r#"##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -245,5 +239,12 @@ noop"#;
 #######.......#######.......#######.....
 "#
         );
+        assert!(part2(SAMPLE1).is_err());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    answer_tests!(Day10);
+}
@@ -1,9 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    println!("Part1: {}", part1(&input).unwrap());
-    println!("Part2: {}", part2(&input).unwrap());
+solution!(Day11, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day11>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug)]
@@ -78,7 +78,8 @@ impl Monkey {
     }
 }
 
-fn execute_round(monkeys: &mut Vec<Monkey>, common_divisor: Option<usize>) {
+/// Plays a round, with a modulus worry levels are kept modulo it instead of being divided by 3
+fn execute_round(monkeys: &mut Vec<Monkey>, modulus: Option<usize>) {
     for i in 0..monkeys.len() {
         let mut val_to_push = vec![];
         let mut monkey_to_receive = vec![];
@@ -94,8 +95,7 @@ fn execute_round(monkeys: &mut Vec<Monkey>, common_divisor: Option<usize>) {
                     OperationType::Add => *item += value,
                     OperationType::Muliply => *item *= value,
                 };
-                match common_divisor {
-                    // Reduce all items by the common divisor if it is set
+                match modulus {
                     Some(x) => *item %= x,
                     None => *item /= 3,
                 }
@@ -139,10 +139,11 @@ fn part2(input: &str) -> R<usize> {
     for monkey in iter {
         monkeys.push(Monkey::new(monkey)?);
     }
-    // All the divisors multiplied together
-    let common_divisor: usize = monkeys.iter().map(|m| m.divisor).product();
+    // Every divisor divides the lcm so worry levels modulo it pass the same tests
+    let divisors = monkeys.iter().map(|m| m.divisor).collect::<Vec<_>>();
+    let modulus = lcm_many(&divisors).expect("lcm of the divisors fits in usize");
     for _round in 0..10_000 {
-        execute_round(&mut monkeys, Some(common_divisor));
+        execute_round(&mut monkeys, Some(modulus));
     }
     let mut inspections = monkeys.into_iter().map(|x| x.inspections).collect::<Vec<_>>();
     inspections.sort_by(|a, b| b.cmp(a));
@@ -152,6 +153,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day11);
     const SAMPLE1: &str = r#"Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
@@ -1,246 +1,108 @@
-use std::vec;
+use std::collections::HashMap;
 
 use common::*;
 
-
-/// This solution has multiple iterations of getting an answer with different timer intervals
+/// This solution has multiple iterations of getting an answer, the slower ones are still checked in the tests
 /// My first part 2 solution was ran single threadded and took ~6 mins on a 7950X
 /// Now my part 2 takes 200ms which is 4x faster than my original *part 1*
 /// # The optimizations I made
 /// - Start the path finding from last to first instead of first to last
 /// - Pre cacluclate all paths for part2 and just do a lookup
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1 from_start");
-        println!("Part1: {}", part1(&input, false).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 1 from_end");
-        println!("Part1: {}", part1(&input, true).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2 from_start");
-        println!("Part2: {}", part2(&input, false).unwrap());
+struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        Ok(input)
     }
-    {
-        let _timer = Timer::new("Part 2 from_end");
-        println!("Part2: {}", part2(&input, true).unwrap());
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        part1(input, true)
     }
-    {
-        let _timer = Timer::new("Part 2 calc all and cache");
-        println!("Part2: {}", part2_calc_all_and_cache(&input).unwrap());
+
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        part2_calc_all_and_cache(input)
     }
 }
 
+fn main() -> R<()> {
+    run::<Day12>(&read_input_file_for_project_as_string!())
+}
+
 struct Map {
-    tiles: Vec<u8>,
-    width: usize,
-    height: usize,
+    tiles: Grid<u8>,
 }
 
 impl Map {
-    fn index_to_x_y(&self, index: usize) -> (isize, isize) {
-        let y = index / self.width;
-        let x = index % self.width;
-        (x as isize, y as isize)
+    /// Parses the heights, returns the map along with the index of the start and end
+    fn parse(input: &str) -> (Self, usize, usize) {
+        let grid = Grid::new(input);
+        let start = grid.inner.iter().position(|x| *x == 'S').unwrap();
+        let end = grid.inner.iter().position(|x| *x == 'E').unwrap();
+        let tiles = grid.map(|&x| match x {
+            'S' => b'a',
+            'E' => b'z',
+            _ => x as u8,
+        });
+        (Map { tiles }, start, end)
     }
 
-    fn get(&self, (x, y): (isize, isize)) -> Option<u8> {
-        if x >= self.width as isize || y >= self.height as isize || x.is_negative() || y.is_negative() {
-            None
-        } else {
-            let y: usize = y as usize;
-            let x: usize = x as usize;
-            Some(self.tiles[y * self.width + x])
-        }
+    fn index_to_point(&self, index: usize) -> Point2<isize> {
+        self.tiles.index_to_point(index).signed()
     }
 
-    fn calc_distance_from_start(&self, start: usize, end: usize) -> R<usize> {
-        let end = self.index_to_x_y(end);
-        let start = Tile {
-            position: self.index_to_x_y(start),
-            height: b'a',
-            distance: 0,
-        };
-        let mut all_tiles = vec![start];
-        let mut num_to_skip = 0;
-        loop {
-            let mut new_tiles: Vec<Tile> = vec![];
-            // only process the new tiles addec
-            for tile in &all_tiles[num_to_skip..all_tiles.len()] {
-                // Check the up down left right for each new tile
-                let up = (tile.position.0, tile.position.1 - 1);
-                let down = (tile.position.0, tile.position.1 + 1);
-                let left = (tile.position.0 - 1, tile.position.1);
-                let right = (tile.position.0 + 1, tile.position.1);
-                for position in [up, down, left, right] {
-                    if let Some(new_tile) =
-                        self.is_tile_new_and_navigable(position, &all_tiles, &new_tiles, tile, false)
-                    {
-                        new_tiles.push(new_tile)
-                    }
-                }
-            }
-            num_to_skip = new_tiles.len();
-            all_tiles.append(&mut new_tiles);
-            if num_to_skip == 0 {
-                // This point cannot reach the end
-                #[cfg(debug)]
-                println!("stuck! {:?}", all_tiles.first().unwrap().position);
-                return Ok(usize::MAX);
-            }
-            if let Some(tile) = all_tiles.iter().find(|x| x.position == end) {
-                #[cfg(debug)]
-                println!("Found  {:?} = {}", all_tiles.first().unwrap().position, tile.distance);
-                return Ok(tile.distance);
-            }
-        }
+    /// Index of every tile at the lowest height
+    fn lowest_tiles(&self) -> Vec<usize> {
+        self.tiles
+            .inner
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == b'a')
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    fn calc_distance_from_end(&self, start: usize, end: usize) -> R<usize> {
-        let start_tile = Tile {
-            position: self.index_to_x_y(end),
-            height: b'z',
-            distance: 0,
-        };
-        let end = self.index_to_x_y(start);
-        let mut all_tiles = vec![start_tile];
-        let mut num_to_skip = 0;
-        loop {
-            let mut new_tiles: Vec<Tile> = vec![];
-            // only process the new tiles addec
-            for tile in &all_tiles[num_to_skip..all_tiles.len()] {
-                // Check the up down left right for each new tile
-                let up = (tile.position.0, tile.position.1 - 1);
-                let down = (tile.position.0, tile.position.1 + 1);
-                let left = (tile.position.0 - 1, tile.position.1);
-                let right = (tile.position.0 + 1, tile.position.1);
-                for position in [up, down, left, right] {
-                    if let Some(new_tile) = self.is_tile_new_and_navigable(position, &all_tiles, &new_tiles, tile, true)
-                    {
-                        new_tiles.push(new_tile)
-                    }
-                }
-            }
-            num_to_skip = new_tiles.len();
-            all_tiles.append(&mut new_tiles);
-            if num_to_skip == 0 {
-                // This point cannot reach the end
-                #[cfg(debug)]
-                println!("stuck! {:?}", all_tiles.first().unwrap().position);
-                return Ok(usize::MAX);
-            }
-            if let Some(tile) = all_tiles.iter().find(|x| x.position == end) {
-                #[cfg(debug)]
-                println!("Found  {:?} = {}", all_tiles.first().unwrap().position, tile.distance);
-                return Ok(tile.distance);
-            }
-        }
+    /// Tiles that can be moved to from the position, when `from_end` the moves are walked backwards
+    fn moves(&self, position: Point2<isize>, from_end: bool) -> Vec<Point2<isize>> {
+        let height = self.tiles.at(position).copied().unwrap();
+        position
+            .neighbors4()
+            .filter(|&next| {
+                self.tiles
+                    .at(next)
+                    .is_some_and(|&u| if from_end { height <= u + 1 } else { u <= height + 1 })
+            })
+            .collect()
     }
 
-    fn calc_all_distances(&mut self, end: usize) -> R<Vec<Tile>> {
-        let start_tile = Tile {
-            position: self.index_to_x_y(end),
-            height: b'z',
-            distance: 0,
-        };
-        let mut all_tiles = vec![start_tile];
-        let mut num_to_skip = 0;
-        loop {
-            let mut new_tiles: Vec<Tile> = vec![];
-            // only process the new tiles addec
-            for tile in &all_tiles[num_to_skip..all_tiles.len()] {
-                // Check the up down left right for each new tile
-                let up = (tile.position.0, tile.position.1 - 1);
-                let down = (tile.position.0, tile.position.1 + 1);
-                let left = (tile.position.0 - 1, tile.position.1);
-                let right = (tile.position.0 + 1, tile.position.1);
-                for position in [up, down, left, right] {
-                    if let Some(new_tile) = self.is_tile_new_and_navigable(position, &all_tiles, &new_tiles, tile, true)
-                    {
-                        new_tiles.push(new_tile);
-                    }
-                }
-            }
-            num_to_skip = new_tiles.len();
-            all_tiles.append(&mut new_tiles);
-            if num_to_skip == 0 {
-                // This point cannot reach the end
-                #[cfg(debug)]
-                println!("stuck! {:?}", all_tiles.first().unwrap().position);
-                return Ok(all_tiles);
-            }
-        }
+    /// Steps from start to end, `usize::MAX` when the end can't be reached
+    fn calc_distance_from_start(&self, start: usize, end: usize) -> R<usize> {
+        let end = self.index_to_point(end);
+        Ok(
+            bfs([self.index_to_point(start)], |&p| self.moves(p, false), |&p| p == end)
+                .map_or(usize::MAX, |path| path.cost),
+        )
     }
-    /// Determines if the new tile
-    fn is_tile_new_and_navigable(
-        &self,
-        position: (isize, isize),
-        all_tiles: &[Tile],
-        new_tiles: &[Tile],
-        tile: &Tile,
-        from_end: bool,
-    ) -> Option<Tile> {
-        match self.get(position) {
-            Some(u) => {
-                let navigable = if from_end {
-                    tile.height <= u + 1
-                } else {
-                    u <= tile.height + 1
-                };
-                if navigable
-                    && !all_tiles.iter().any(|f| f.position == position) // Check for new
-                    && !new_tiles.iter().any(|f| f.position == position)
-                {
-                    Some(Tile {
-                        position,
-                        height: u,
-                        distance: tile.distance + 1,
-                    })
-                } else {
-                    None
-                }
-            }
-            None => None,
-        }
+
+    fn calc_distance_from_end(&self, start: usize, end: usize) -> R<usize> {
+        let start = self.index_to_point(start);
+        Ok(
+            bfs([self.index_to_point(end)], |&p| self.moves(p, true), |&p| p == start)
+                .map_or(usize::MAX, |path| path.cost),
+        )
     }
-}
 
-#[derive(Clone, Copy)]
-struct Tile {
-    position: (isize, isize),
-    height: u8,
-    distance: usize,
+    /// Steps to the end from every tile that can reach it
+    fn calc_all_distances(&self, end: usize) -> HashMap<Point2<isize>, usize> {
+        bfs_distances([self.index_to_point(end)], |&p| self.moves(p, true))
+    }
 }
 
 fn part1(input: &str, from_end: bool) -> R<usize> {
-    let mut tiles: Vec<char> = vec![];
-    let mut width = None;
-    let height = input.lines().count();
-    for line in input.lines() {
-        if width.is_none() {
-            width = Some(line.len());
-        }
-        for c in line.chars() {
-            tiles.push(c);
-        }
-    }
-    let start = tiles.iter().position(|x| *x == 'S').unwrap();
-    let end = tiles.iter().position(|x| *x == 'E').unwrap();
-    let tiles: Vec<u8> = tiles
-        .into_iter()
-        .map(|x| match x {
-            'S' => b'a',
-            'E' => b'z',
-            _ => x as u8,
-        })
-        .collect();
-    let map = Map {
-        tiles,
-        width: width.unwrap(),
-        height,
-    };
+    let (map, start, end) = Map::parse(input);
     if from_end {
         map.calc_distance_from_end(start, end)
     } else {
@@ -248,44 +110,17 @@ fn part1(input: &str, from_end: bool) -> R<usize> {
     }
 }
 
+// The slower versions of part 2, only kept to check the cached one in the tests
+#[cfg(test)]
 fn part2(input: &str, from_end: bool) -> R<usize> {
-    let mut tiles: Vec<char> = vec![];
-    let mut width = None;
-    let height = input.lines().count();
-    for line in input.lines() {
-        if width.is_none() {
-            width = Some(line.len());
-        }
-        for c in line.chars() {
-            tiles.push(c);
-        }
-    }
-    let end = tiles.iter().position(|x| *x == 'E').unwrap();
-    let tiles: Vec<u8> = tiles
-        .into_iter()
-        .map(|x| match x {
-            'S' => b'a',
-            'E' => b'z',
-            _ => x as u8,
-        })
-        .collect();
+    let (map, _, end) = Map::parse(input);
     // Get all the start indexes
-    let starts = tiles
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| **c == b'a')
-        .map(|(i, _)| i.to_owned())
-        .collect::<Vec<_>>();
+    let starts = map.lowest_tiles();
     // Run all calculations in parallel and return the minimum
     if from_end {
         Ok(starts
             .into_par_iter()
             .map(|start| {
-                let map = Map {
-                    tiles: tiles.clone(),
-                    width: width.unwrap(),
-                    height,
-                };
                 let dist = map.calc_distance_from_end(start, end).unwrap();
                 dist
             })
@@ -295,11 +130,6 @@ fn part2(input: &str, from_end: bool) -> R<usize> {
         Ok(starts
             .into_par_iter()
             .map(|start| {
-                let map = Map {
-                    tiles: tiles.clone(),
-                    width: width.unwrap(),
-                    height,
-                };
                 let dist = map.calc_distance_from_start(start, end).unwrap();
                 dist
             })
@@ -309,61 +139,20 @@ fn part2(input: &str, from_end: bool) -> R<usize> {
 }
 
 fn part2_calc_all_and_cache(input: &str) -> R<usize> {
-    let mut tiles: Vec<char> = vec![];
-    let mut width = None;
-    let height = input.lines().count();
-    for line in input.lines() {
-        if width.is_none() {
-            width = Some(line.len());
-        }
-        for c in line.chars() {
-            tiles.push(c);
-        }
-    }
-    let end = tiles.iter().position(|x| *x == 'E').unwrap();
-    let tiles: Vec<u8> = tiles
+    let (map, _, end) = Map::parse(input);
+    let distances = map.calc_all_distances(end);
+    Ok(map
+        .lowest_tiles()
         .into_iter()
-        .map(|x| match x {
-            'S' => b'a',
-            'E' => b'z',
-            _ => x as u8,
-        })
-        .collect();
-    // Get all the start indexes
-    let starts = tiles
-        .iter()
-        .enumerate()
-        .filter(|(_, c)| **c == b'a')
-        .map(|(i, _)| i.to_owned())
-        .collect::<Vec<_>>();
-    let mut min = usize::MAX;
-
-    let mut map = Map {
-        tiles,
-        width: width.unwrap(),
-        height,
-    };
-    let tiles = map.calc_all_distances(end).unwrap();
-    for start in starts {
-        match tiles
-            .iter()
-            .find(|x| map.index_to_x_y(start) == x.position)
-            .map(|x| x.distance)
-        {
-            Some(distance) => {
-                if distance < min {
-                    min = distance;
-                }
-            }
-            None => continue,
-        }
-    }
-
-    Ok(min)
+        .filter_map(|start| distances.get(&map.index_to_point(start)).copied())
+        .min()
+        .unwrap_or(usize::MAX))
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day12);
     const SAMPLE1: &str = r#"Sabqponm
 abcryxxl
 accszExk
@@ -372,9 +161,37 @@ abdefghi"#;
     #[test]
     fn p1_test() {
         assert_eq!(part1(SAMPLE1, true).unwrap(), 31);
+        assert_eq!(part1(SAMPLE1, false).unwrap(), 31);
     }
     #[test]
     fn p2_test() {
         assert_eq!(part2(SAMPLE1, true).unwrap(), 29);
+        assert_eq!(Day12::solve_part2(SAMPLE1).unwrap(), 29);
+    }
+    #[test]
+    fn cached_difftest() {
+        // Heights rise a step at a time towards the end on the right, with some pits that can't be climbed out of
+        DiffTest::new(
+            |input: &String| part2(input, false).unwrap(),
+            |input| part2_calc_all_and_cache(input).unwrap(),
+            |rng| {
+                let (rows, cols): (usize, usize) = (rng.gen_range(2..6), rng.gen_range(26..40));
+                let mut tiles = (0..rows)
+                    .map(|_| {
+                        (0..cols)
+                            .map(|col| {
+                                let pit = if rng.gen_ratio(1, 10) { 3 } else { 0 };
+                                (b'a' + (col * 25 / (cols - 1)).saturating_sub(pit) as u8) as char
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                tiles[rng.gen_range(0..rows)][cols - 1] = 'E';
+                tiles[rng.gen_range(0..rows)][0] = 'S';
+                tiles.iter().map(|row| row.iter().collect::<String>()).join("\n")
+            },
+        )
+        .run()
+        .unwrap();
     }
 }
@@ -5,16 +5,10 @@ use std::{fmt::Display, vec};
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day13, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day13>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug)]
@@ -78,6 +72,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day13);
     const SAMPLE1: &str = r#"[1,1,3,1,1]
 [1,1,5,1,1]
 
@@ -1,15 +1,11 @@
+use std::ops::RangeInclusive;
+
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day14, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day14>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -28,6 +24,28 @@ impl CaveMatter {
             Air | SandEmitter => false,
         }
     }
+
+    fn to_char(&self) -> char {
+        match self {
+            Rock => '#',
+            Air => '.',
+            Sand => 'o',
+            SandEmitter => '+',
+        }
+    }
+}
+
+/// Columns `xs` of the top `rows` rows of the cave
+fn cave_frame(cave_map: &[CaveMatter], xs: RangeInclusive<usize>, rows: usize) -> Frame {
+    let cols = xs.clone().count();
+    let cells = (0..rows)
+        .flat_map(|y| xs.clone().map(move |x| cave_map[x_y_to_index(x, y)].to_char()))
+        .collect();
+    Frame::new(Grid::from_vec(cells, cols)).with_colors(|c| match c {
+        'o' => Some(Color::Yellow),
+        '+' => Some(Color::Red),
+        _ => None,
+    })
 }
 
 fn x_y_to_index(x: usize, y: usize) -> usize {
@@ -108,24 +126,26 @@ fn part1(input: &str) -> R<usize> {
         }
     }
 
-    // Keep adding sand until no longer stable
-    while sand_is_stable(&mut cave_map, 500, 0) {}
-    #[cfg(test)]
-    {
-        // Print out the map in the test for sanity checking
-        for (i, cave_spot) in cave_map.iter().enumerate() {
-            let c = match cave_spot {
-                Rock => '#',
-                Air => '.',
-                Sand => 'o',
-                SandEmitter => '+',
-            };
-            print!("{}", c);
-            if i % 1000 == 999 {
-                println!();
-            }
+    // Keep adding sand until no longer stable, each grain is a frame when visualizing
+    let mut animation = Animation::new();
+    let (xs, rows) = if visualizing() {
+        let rocks = cave_map
+            .iter()
+            .positions(|x| *x == Rock)
+            .map(index_to_x_y)
+            .collect_vec();
+        let (min_x, max_x) = rocks.iter().map(|r| r.0).minmax().into_option().unwrap_or((500, 500));
+        let max_y = rocks.iter().map(|r| r.1).max().unwrap_or(0);
+        (min_x - 1..=max_x + 1, max_y + 2)
+    } else {
+        (500..=500, 1)
+    };
+    while sand_is_stable(&mut cave_map, 500, 0) {
+        if visualizing() {
+            animation.push(cave_frame(&cave_map, xs.clone(), rows));
         }
     }
+    animation.show_part(1)?;
     Ok(cave_map.into_iter().filter(|x| *x == Sand).count())
 }
 
@@ -166,29 +186,33 @@ fn part2(input: &str) -> R<usize> {
         cave_map[x_y_to_index(x, y)] = Rock;
     }
 
-    // Keep adding sand until no longer stable
-    while sand_is_stable(&mut cave_map, 500, 0) {}
-    #[cfg(test)]
-    {
-        // Print out the map in the test for sanity checking
-        for (i, cave_spot) in cave_map.iter().enumerate() {
-            let c = match cave_spot {
-                Rock => '#',
-                Air => '.',
-                Sand => 'o',
-                SandEmitter => '+',
-            };
-            print!("{}", c);
-            if i % 1000 == 999 {
-                println!();
-            }
+    // Keep adding sand until no longer stable, sand can pile out as far from the emitter as the floor is deep
+    let mut animation = Animation::new();
+    let (xs, rows) = if visualizing() {
+        (
+            500usize.saturating_sub(greatest_y + 3)..=(500 + greatest_y + 3).min(999),
+            greatest_y + 3,
+        )
+    } else {
+        (500..=500, 1)
+    };
+    let mut grains = 0;
+    while sand_is_stable(&mut cave_map, 500, 0) {
+        grains += 1;
+        if visualizing() && grains % 500 == 0 {
+            animation.push(cave_frame(&cave_map, xs.clone(), rows));
         }
     }
+    if visualizing() {
+        animation.push(cave_frame(&cave_map, xs, rows));
+        animation.show_part(2)?;
+    }
     Ok(cave_map.into_iter().filter(|x| *x == Sand).count())
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day14);
     const SAMPLE1: &str = r#"498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9"#;
     #[test]
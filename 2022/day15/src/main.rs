@@ -2,30 +2,32 @@ use std::collections::HashSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input, 2_000_000).unwrap());
+struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        Ok(input)
     }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input, 4_000_000).unwrap());
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        part1(input, 2_000_000)
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: isize,
-    y: isize,
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        part2(input, 4_000_000)
+    }
 }
 
-impl Point {
-    fn calculate_manhatten_distance(&self, b: &Point) -> usize {
-        self.x.abs_diff(b.x) + self.y.abs_diff(b.y)
-    }
+fn main() -> R<()> {
+    run::<Day15>(&read_input_file_for_project_as_string!())
 }
 
+type Point = Point2<isize>;
+
 fn parse_input(input: &str) -> R<Vec<(Point, Point)>> {
     let mut points = vec![];
     for line in input.lines() {
@@ -54,12 +56,12 @@ fn part1(input: &str, y_val: isize) -> R<usize> {
     let points = parse_input(input)?;
     // These hash sets will be all the X coords in `y_val`
     let mut beacons = HashSet::new();
-    let mut not_beacons = HashSet::new();
+    let mut not_beacons = IntervalSet::new();
     for (sensor, beacon) in points {
         if beacon.y == y_val {
             beacons.insert(beacon.x);
         }
-        let radius = sensor.calculate_manhatten_distance(&beacon) as isize;
+        let radius = sensor.manhattan(&beacon);
         // For each Y difference the radious will be reduced by 1
         let width_at_y_coordinate = radius - sensor.y.abs_diff(y_val) as isize;
         if width_at_y_coordinate < 0 {
@@ -70,43 +72,31 @@ fn part1(input: &str, y_val: isize) -> R<usize> {
         let first = sensor.x - width_at_y_coordinate;
         let last = sensor.x + width_at_y_coordinate;
         // Insert all confirmed not beacons known
-        for x in first..=last {
-            not_beacons.insert(x);
-        }
+        not_beacons.insert(first..=last);
     }
     // Return number of not_beacons minus number of confirmed beacons
-    Ok(not_beacons.len() - beacons.len())
+    Ok(not_beacons.len() as usize - beacons.len())
 }
 
 fn part2(input: &str, max_coord: isize) -> R<usize> {
     let points = parse_input(input)?;
-    // Go through every point and find one not covered
-    let mut x = 0;
-    let mut y = 0;
-    'next_coord: while y <= max_coord {
-        // If x goes past max, move y down and reset x
-        if x > max_coord {
-            x = 0;
-            y += 1;
-        }
+    let row = [0..=max_coord].into_iter().collect::<IntervalSet<_>>();
+    for y in 0..=max_coord {
+        // Every X coord in this row covered by a sensor
+        let mut covered = IntervalSet::new();
         for (sensor, beacon) in points.iter() {
-            let radius = sensor.calculate_manhatten_distance(beacon) as isize;
+            let radius = sensor.manhattan(beacon);
             let width_at_y_coordinate = radius - sensor.y.abs_diff(y) as isize;
             if width_at_y_coordinate < 0 {
                 // This sensor doesn't cover this line
                 continue;
             }
-            // This sensor does cover this line
-            let first = sensor.x - width_at_y_coordinate;
-            let last = sensor.x + width_at_y_coordinate;
-            if first <= x && x <= last {
-                // This point is covered go next
-                x = last + 1; // Move x forward to the next not covered index save calculations
-                continue 'next_coord;
-            }
+            covered.insert(sensor.x - width_at_y_coordinate..=sensor.x + width_at_y_coordinate);
+        }
+        // The distress beacon is the only gap left in the row
+        if let Some(x) = row.difference(&covered).min() {
+            return Ok(x as usize * 4_000_000 + y as usize);
         }
-        // If this point is not covered it wll fall through
-        return Ok(x as usize * 4_000_000 + y as usize);
     }
 
     Err(Box::new(AdventOfCodeError::new("Not found")))?
@@ -115,6 +105,7 @@ fn part2(input: &str, max_coord: isize) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day15);
     const SAMPLE1: &str = r#"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
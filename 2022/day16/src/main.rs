@@ -3,16 +3,10 @@ use common::*;
 use petgraph::{algo::dijkstra, stable_graph::NodeIndex, Graph};
 use std::{collections::HashMap, fmt::Debug};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day16, u64, u64);
+
+fn main() -> R<()> {
+    run::<Day16>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug, Clone)]
@@ -242,6 +236,7 @@ fn part2(input: &str) -> R<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day16);
     const SAMPLE1: &str = r#"Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
 Valve CC has flow rate=2; tunnels lead to valves DD, BB
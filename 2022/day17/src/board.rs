@@ -1,3 +1,5 @@
+use common::{bfs_distances, Color, Frame, Grid};
+
 const WIDTH: usize = 7;
 const MAX_PIECE_HEIGHT: usize = 4;
 const SPAWN_X_OFFSET: usize = 2;
@@ -37,11 +39,6 @@ impl Board {
             }
         }
     }
-    #[inline]
-    pub fn x_y_to_index(x: usize, y: usize) -> usize {
-        y * WIDTH + x
-    }
-
     pub fn new() -> Self {
         let height = 0;
         let data = vec![false; (height + HEAD_ROOM) * WIDTH];
@@ -52,6 +49,29 @@ impl Board {
             current_piece_location: (SPAWN_X_OFFSET, height + SPAWN_Y_OFFSET),
         }
     }
+    /// Empty squares a rock could still fall into, found by filling down from above the top of the tower. Squares
+    /// are given as (x, depth below the top) so towers with the same surface match at any height
+    pub fn surface(&self) -> Vec<(isize, isize)> {
+        let top = self.height as isize;
+        let reachable = bfs_distances((0..WIDTH as isize).map(|x| (x, top)), |&(x, y)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| !self.read_position(x, y) && y <= top)
+                .collect::<Vec<_>>()
+        });
+        let mut surface = reachable.into_keys().map(|(x, y)| (x, top - y)).collect::<Vec<_>>();
+        surface.sort_unstable();
+        surface
+    }
+    /// Top `rows` rows of the tower and the space above it, drawn with the top of the tower at the top
+    pub fn frame(&self, rows: usize) -> Frame {
+        let cells = (self.height.saturating_sub(rows)..self.height + HEAD_ROOM)
+            .rev()
+            .flat_map(|y| self.data[y * WIDTH..(y + 1) * WIDTH].iter())
+            .map(|&rock| if rock { '#' } else { '.' })
+            .collect();
+        Frame::new(Grid::from_vec(cells, WIDTH)).with_colors(|c| (c == '#').then_some(Color::Yellow))
+    }
     pub fn spawn_new_rock(&mut self, last_rock_height: usize) {
         // Increase size of the data to account for new rock
         self.height = self.height.max(last_rock_height + 1);
@@ -68,38 +88,3 @@ impl Board {
         self.current_piece_location = (SPAWN_X_OFFSET, (self.height) + SPAWN_Y_OFFSET);
     }
 }
-
-impl std::fmt::Display for Board {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Print the bottom
-        let mut a = vec![];
-        a.push("+-------+\n".to_string());
-        for (i, val) in self.data.iter().enumerate() {
-            let mut c = match *val {
-                true => '#',
-                false => '.',
-            };
-            let (x, y) = self.current_piece_location;
-            if Board::x_y_to_index(x, y) == i {
-                c = 'x';
-            }
-            if i % 7 == 0 {
-                a.push(format!("|{c}"));
-            } else if i % 7 == 6 {
-                a.push(format!("{c}|\n"));
-            } else {
-                a.push(format!("{c}"));
-            }
-        }
-        let st = a
-            .join("")
-            .chars()
-            .collect::<String>()
-            .lines()
-            .into_iter()
-            .rev()
-            .collect::<Vec<_>>()
-            .join("\n");
-        f.write_fmt(format_args!("{}", st))
-    }
-}
@@ -1,19 +1,11 @@
-use std::collections::{hash_map::Entry::Vacant, HashMap};
-
 use board::Board;
 use common::*;
 use shapes::Shapes;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day17, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day17>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug)]
@@ -36,63 +28,25 @@ impl HorizontalMovement {
 mod board;
 mod shapes;
 
-fn part1(input: &str) -> R<usize> {
-    // List of shapes in order of spawning
-    let shapes = [
-        Shapes::Rect(4, 1),
-        Shapes::Cross,
-        Shapes::L,
-        Shapes::Rect(1, 4),
-        Shapes::Rect(2, 2),
-    ];
-    let jet_patterns = input
-        .chars()
-        .filter(|c| matches!(c, '<' | '>'))
-        .map(HorizontalMovement::new)
-        .collect::<Vec<_>>();
-    let mut board = Board::new();
-    let mut jet_index = 0;
-    for i in 0..=2022 {
-        //println!("\n-------\n{board}\n");
-        let rock = &shapes[i % shapes.len()];
-        loop {
-            // Move rock left or right
-            match jet_patterns[jet_index] {
-                Left => {
-                    rock.move_left(&mut board);
-                    //  println!("<-\n{board}\n");
-                }
-                Right => {
-                    rock.move_right(&mut board);
-                    //   println!("->\n{board}\n");
-                }
-            }
-            jet_index = (jet_index + 1) % jet_patterns.len();
-            // Move rock down and potentionally place it
-            if !rock.move_down(&mut board) {
-                let last_rock_height = rock.turn_to_rock(&mut board);
-                board.spawn_new_rock(last_rock_height);
-                break;
-            } else {
-                board.current_piece_location.1 -= 1;
-            }
-            // println!("v\n{board}\n"
-        }
-    }
-    // Subtract 2, because the height is also 1 higher than it should be (for piece spawning reasons).
-    //Idk why you need to subtract the other 1. Probably 1 index vs 0 index idk?
-    Ok(board.height - 2)
-}
+/// List of shapes in order of spawning
+const SHAPES: [Shapes; 5] = [
+    Shapes::Rect(4, 1),
+    Shapes::Cross,
+    Shapes::L,
+    Shapes::Rect(1, 4),
+    Shapes::Rect(2, 2),
+];
 
-fn part2(input: &str) -> R<usize> {
-    // List of shapes in order of spawning
-    let shapes = [
-        Shapes::Rect(4, 1),
-        Shapes::Cross,
-        Shapes::L,
-        Shapes::Rect(1, 4),
-        Shapes::Rect(2, 2),
-    ];
+/// Rocks dropped looking for the tower to repeat before giving up
+const MAX_ROCKS: usize = 1_000_000;
+
+/// Drops rocks onto the tower until `stop` returns true, returns the height of the tower after each number of rocks.
+///
+/// `stop` is called before each rock falls with the number of rocks so far, the next jet and the tower
+fn simulate<F>(input: &str, mut stop: F) -> Vec<usize>
+where
+    F: FnMut(usize, usize, &Board) -> bool,
+{
     let jet_patterns = input
         .chars()
         .filter(|c| matches!(c, '<' | '>'))
@@ -100,13 +54,15 @@ fn part2(input: &str) -> R<usize> {
         .collect::<Vec<_>>();
     let mut board = Board::new();
     let mut jet_index = 0;
-    let mut differences = HashMap::new();
-    let mut diff_found = false;
+    // Height of the tower after each number of rocks
+    let mut heights = vec![];
     let mut rocks_simulated = 0;
-    let mut height_offset = 0;
-    while rocks_simulated <= 1_000_000_000_000 {
-        let rock_index = rocks_simulated % shapes.len();
-        let rock = &shapes[rock_index];
+    loop {
+        heights.push(board.height);
+        if stop(rocks_simulated, jet_index, &board) {
+            return heights;
+        }
+        let rock = &SHAPES[rocks_simulated % SHAPES.len()];
         loop {
             // Move rock left or right
             match jet_patterns[jet_index] {
@@ -122,43 +78,47 @@ fn part2(input: &str) -> R<usize> {
             if rock.move_down(&mut board) {
                 board.current_piece_location.1 -= 1;
             } else {
-                rocks_simulated += 1;
                 let last_rock_height = rock.turn_to_rock(&mut board);
                 board.spawn_new_rock(last_rock_height);
-                let key = (rock_index, jet_index);
-                if board.height > 2 && !diff_found {
-                    // Use a hashmap to save the rock and jet index with the height and number of rocks
-                    if let Vacant(e) = differences.entry(key) {
-                        e.insert(vec![(board.height, rocks_simulated)]);
-                    } else if let Some(val) = differences.get_mut(&key) {
-                        val.push((board.height, rocks_simulated))
-                    }
-                    let heights = &differences[&key];
-                    let x = heights.len() - 1;
-                    // Try and find a repeating pattern
-                    if heights.len() > 2 && heights[x].0 - heights[x - 1].0 == heights[x - 1].0 - heights[x - 2].0 {
-                        // If the pattern was found, calculate the height_offset and increase the rocks simulated by a lot
-                        diff_found = true;
-                        let rocks_left = 1_000_000_000_000 - rocks_simulated;
-                        let repeat_dist = heights[x].0 - heights[x - 1].0;
-                        let num_rocks = heights[x].1 - heights[x - 1].1;
-                        let (can_simulate, num_left) = (rocks_left / num_rocks, rocks_left % num_rocks);
-                        height_offset = can_simulate * repeat_dist;
-                        rocks_simulated = 1_000_000_000_000 - num_left;
-                    }
-                }
                 break;
             }
         }
+        rocks_simulated += 1;
     }
-    // Subtract 2, because the height is also 1 higher than it should be (for piece spawning reasons).
-    //Idk why you need to subtract the other 1. Probably 1 index vs 0 index idk?
-    Ok(board.height - 2 + height_offset + 1) // Idk why I need +1 but it makes the unit test pass
+}
+
+fn part1(input: &str) -> R<usize> {
+    let mut animation = Animation::new();
+    let heights = simulate(input, |rocks, _, board| {
+        if visualizing() {
+            animation.push(
+                board
+                    .frame(20)
+                    .with_caption(format!("Rock {}, height {}", rocks, board.height)),
+            );
+        }
+        rocks == 2022
+    });
+    animation.show()?;
+    Ok(heights[2022])
+}
+
+fn part2(input: &str) -> R<usize> {
+    let mut detector = CycleDetector::new();
+    let mut cycle = None;
+    let heights = simulate(input, |rocks, jet_index, board| {
+        // The next rock, the next jet and the spaces a rock can still fall into decide how the tower grows from here
+        cycle = detector.push((rocks % SHAPES.len(), jet_index, board.surface()));
+        cycle.is_some() || rocks == MAX_ROCKS
+    });
+    let cycle = cycle.ok_or_else(|| AdventOfCodeError::new("The tower never repeats"))?;
+    Ok(cycle.extrapolate(&heights, 1_000_000_000_000))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day17);
     const SAMPLE1: &str = r#">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"#;
     #[test]
     fn p1_test() {
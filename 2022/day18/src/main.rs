@@ -2,22 +2,17 @@ use std::{thread::Builder, vec};
 
 use common::*;
 
-fn main() {
+solution!(Day18, usize, usize);
+
+fn main() -> R<()> {
     let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    // Make a bigger stack to recurse deeper :)
+    // Make a bigger stack to recurse deeper in part 2 :)
     Builder::new()
         .stack_size(1024 * 1024 * 10)
-        .spawn(move || {
-            let _timer = Timer::new("Part 2");
-            println!("Part2: {}", part2(&input).unwrap());
-        })
+        .spawn(move || run::<Day18>(&input))
         .unwrap()
         .join()
-        .unwrap();
+        .unwrap()
 }
 
 type Cube = Vec<Vec<Vec<BlockType>>>;
@@ -167,6 +162,7 @@ fn fill_with_water(cube: &mut Cube) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day18);
     const SAMPLE2: &str = r#"1,1,1
 2,1,1"#;
     const SAMPLE1: &str = r#"2,2,2
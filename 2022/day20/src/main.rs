@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day20, i64, i64);
+
+fn main() -> R<()> {
+    run::<Day20>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<i64> {
@@ -62,6 +56,7 @@ fn part2(input: &str) -> R<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day20);
     const SAMPLE1: &str = r#"1
 2
 -3
@@ -78,6 +73,62 @@ mod tests {
     fn p2_test() {
         assert_eq!(part2(SAMPLE1).unwrap(), 1623178306);
     }
+
+    /// Numbers with repeats and exactly one 0
+    struct MixNumbers {
+        len: usize,
+    }
+
+    impl InputGenerator for MixNumbers {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            let mut numbers = (1..self.len)
+                .map(|_| rng.gen_range(1..30) * if rng.gen() { 1 } else { -1 })
+                .collect_vec();
+            numbers.insert(rng.gen_range(0..=numbers.len()), 0);
+            numbers.iter().join("\n")
+        }
+    }
+
+    /// Mixes by swapping each number with its neighbour one step at a time
+    fn swap_mix(input: &str) -> i64 {
+        let mut numbers = input
+            .lines()
+            .map(|l| l.parse::<i64>().unwrap())
+            .enumerate()
+            .collect_vec();
+        let len = numbers.len();
+        for index in 0..len {
+            let mut pos = numbers.iter().position(|&(i, _)| i == index).unwrap();
+            // Going all the way round passes every other number and ends up in the same order
+            for _ in 0..numbers[pos].1.rem_euclid(len as i64 - 1) {
+                numbers.swap(pos, (pos + 1) % len);
+                pos = (pos + 1) % len;
+            }
+        }
+        let zero = numbers.iter().position(|&(_, x)| x == 0).unwrap();
+        [1000, 2000, 3000]
+            .iter()
+            .map(|offset| numbers[(zero + offset) % len].1)
+            .sum()
+    }
+
+    #[test]
+    fn swap_difftest() {
+        DiffTest::for_generator(
+            |input| swap_mix(input),
+            |input| part1(input).unwrap(),
+            MixNumbers { len: 12 },
+        )
+        // Keep the 0 and at least one number to move around it
+        .with_shrink(|input| {
+            shrink_lines(input)
+                .into_iter()
+                .filter(|input| input.lines().count() > 1 && input.lines().contains(&"0"))
+                .collect()
+        })
+        .run()
+        .unwrap();
+    }
     #[test]
     fn modulus() {
         let a: isize = 7; // or any other integer type
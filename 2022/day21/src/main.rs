@@ -91,16 +91,10 @@ impl SolveYell2 for Yells2 {
     }
 }
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day21, isize, String);
+
+fn main() -> R<()> {
+    run::<Day21>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<isize> {
@@ -164,6 +158,7 @@ fn part2(input: &str) -> R<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day21);
     const SAMPLE1: &str = r#"root: pppw + sjmn
 dbpl: 5
 cczh: sllz + lgvd
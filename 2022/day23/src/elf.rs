@@ -1,16 +1,16 @@
-use crate::{point::Point, Direction};
+use common::*;
 
 pub struct Elf {
-    // Where this elf is
-    pub location: Point,
+    // Where this elf is, y grows going south
+    pub location: Point2<isize>,
     // How this elf wants to move
-    pub proposal: Option<Point>,
+    pub proposal: Option<Point2<isize>>,
 }
 
 impl Elf {
     pub const fn new(x: isize, y: isize) -> Self {
         Self {
-            location: Point { x, y },
+            location: Point2::new(x, y),
             proposal: None,
         }
     }
@@ -21,53 +21,27 @@ impl Elf {
         }
     }
 
-    pub fn propose_movement(&self, elf_locations: &[Point], index: usize) -> Option<Point> {
-        use crate::Direction::*;
-        const DIRECTIONS: [Direction; 4] = [North, South, West, East];
+    pub fn propose_movement(&self, elf_locations: &[Point2<isize>], index: usize) -> Option<Point2<isize>> {
+        const DIRECTIONS: [Dir4; 4] = [Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right];
+        let is_free = |dir: Dir8| elf_locations.binary_search(&self.location.step(dir)).is_err();
 
         // Check if is isolated
-        if Point::get_adjacent_offsets()
-            .iter()
-            .all(|p| elf_locations.binary_search(&(*p + self.location)).is_err())
-        {
+        if Dir8::ALL.into_iter().all(is_free) {
             return None;
         }
 
-        // Check the directions in order, based on the index (round number)
+        // Check the directions in order, based on the index (round number), a direction is free when the 3 cells
+        // on that side are
         // Ex: Round1 N S W E
         //     Round2 S W E N
-        let direction1 = DIRECTIONS[index % 4];
-        let direction2 = DIRECTIONS[(index + 1) % 4];
-        let direction3 = DIRECTIONS[(index + 2) % 4];
-        let direction4 = DIRECTIONS[(index + 3) % 4];
-        let mut direction = None;
-        if Point::get_offset_for_direction(direction1)
-            .iter()
-            .all(|p| elf_locations.binary_search(&(*p + self.location)).is_err())
-        {
-            direction = Some(direction1);
-        } else if Point::get_offset_for_direction(direction2)
-            .iter()
-            .all(|p| elf_locations.binary_search(&(*p + self.location)).is_err())
-        {
-            direction = Some(direction2);
-        } else if Point::get_offset_for_direction(direction3)
-            .iter()
-            .all(|p| elf_locations.binary_search(&(*p + self.location)).is_err())
-        {
-            direction = Some(direction3);
-        } else if Point::get_offset_for_direction(direction4)
-            .iter()
-            .all(|p| elf_locations.binary_search(&(*p + self.location)).is_err())
-        {
-            direction = Some(direction4);
-        }
-        match direction {
-            Some(North) => Some(self.location + Point::new(0, 1)),
-            Some(South) => Some(self.location + Point::new(0, -1)),
-            Some(East) => Some(self.location + Point::new(1, 0)),
-            Some(West) => Some(self.location + Point::new(-1, 0)),
-            None => None,
-        }
+        (0..4)
+            .map(|i| DIRECTIONS[(index + i) % 4])
+            .find(|&direction| {
+                let direction = Dir8::from(direction);
+                [direction.turn_left(), direction, direction.turn_right()]
+                    .into_iter()
+                    .all(is_free)
+            })
+            .map(|direction| self.location.step(direction))
     }
 }
@@ -1,33 +1,17 @@
 use common::*;
 use elf::Elf;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day23, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day23>(&read_input_file_for_project_as_string!())
 }
 
 mod elf;
-mod point;
-
-#[derive(Debug, Clone, Copy)]
-pub enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
 
 fn parse_initial_conditions(input: &str) -> Vec<Elf> {
     let mut elves = vec![];
-    // Reverse the input so positive Y is North, easier to wrap my head around
-    for (y, line) in input.lines().rev().enumerate() {
+    for (y, line) in input.lines().enumerate() {
         for (x, char) in line.chars().enumerate() {
             if char == '#' {
                 elves.push(Elf::new(x.try_into().unwrap(), y.try_into().unwrap()));
@@ -37,10 +21,36 @@ fn parse_initial_conditions(input: &str) -> Vec<Elf> {
     elves
 }
 
+/// Smallest rectangle holding every elf
+fn elves_frame(elves: &[Elf]) -> Frame {
+    let (min_x, max_x) = elves
+        .iter()
+        .map(|elf| elf.location.x)
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+    let (min_y, max_y) = elves
+        .iter()
+        .map(|elf| elf.location.y)
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+    let cols = (max_x - min_x + 1) as usize;
+    let mut grid = Grid::filled((max_y - min_y + 1) as usize, cols, '.');
+    for elf in elves {
+        grid[Point2::new((elf.location.x - min_x) as usize, (elf.location.y - min_y) as usize)] = '#';
+    }
+    Frame::new(grid).with_colors(|c| (c == '#').then_some(Color::Green))
+}
+
 fn part1(input: &str) -> R<usize> {
     let mut elves = parse_initial_conditions(input);
     let num_rounds = 10;
+    let mut animation = Animation::new();
     for round in 0..num_rounds {
+        if visualizing() {
+            animation.push(elves_frame(&elves).with_caption(format!("Round {}", round)));
+        }
         let mut elf_positions = elves.iter().map(|x| x.location).collect::<Vec<_>>();
         elf_positions.sort();
         // First half of round
@@ -65,6 +75,10 @@ fn part1(input: &str) -> R<usize> {
             }
         }
     }
+    if visualizing() {
+        animation.push(elves_frame(&elves).with_caption(format!("Round {}", num_rounds)));
+        animation.show()?;
+    }
     let xs = elves.iter().map(|elf| elf.location.x);
     let ys = elves.iter().map(|elf| elf.location.y);
 
@@ -116,7 +130,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use point::Point;
+    answer_tests!(Day23);
     const SAMPLE1: &str = r#"....#..
 ..###.#
 #...#.#
@@ -150,7 +164,7 @@ mod tests {
     #[test]
     fn elf_isolated() {
         let elf = Elf::new(0, 0);
-        let mut locations = vec![Point::new(0, 0)];
+        let mut locations = vec![Point2::new(0, 0)];
         locations.sort();
         assert_eq!(elf.propose_movement(&locations, 0), None);
     }
@@ -158,42 +172,47 @@ mod tests {
     #[test]
     fn elf_move_north() {
         let elf = Elf::new(0, 0);
-        let mut locations = vec![Point::new(0, 0), Point::new(1, 0)];
+        let mut locations = vec![Point2::new(0, 0), Point2::new(1, 0)];
         locations.sort();
 
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::NORTH));
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(0, -1)));
     }
 
     #[test]
     fn elf_move_south() {
         let elf = Elf::new(0, 0);
-        let mut locations = vec![Point::new(0, 0), Point::new(0, 1)];
+        let mut locations = vec![Point2::new(0, 0), Point2::new(0, -1)];
         locations.sort();
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::SOUTH));
-        let mut locations = vec![Point::new(0, 0), Point::new(-1, 1)];
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(0, 1)));
+        let mut locations = vec![Point2::new(0, 0), Point2::new(-1, -1)];
         locations.sort();
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::SOUTH));
-        let mut locations = vec![Point::new(0, 0), Point::new(1, 1)];
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(0, 1)));
+        let mut locations = vec![Point2::new(0, 0), Point2::new(1, -1)];
         locations.sort();
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::SOUTH));
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(0, 1)));
     }
 
     #[test]
     fn elf_move_west() {
         let elf = Elf::new(0, 0);
-        let mut locations = vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, -1)];
+        let mut locations = vec![Point2::new(0, 0), Point2::new(0, -1), Point2::new(0, 1)];
         locations.sort();
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::WEST));
-        let mut locations = vec![Point::new(0, 0), Point::new(0, 1), Point::new(1, -1)];
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(-1, 0)));
+        let mut locations = vec![Point2::new(0, 0), Point2::new(0, -1), Point2::new(1, 1)];
         locations.sort();
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::WEST));
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(-1, 0)));
     }
     #[test]
     fn elf_move_east() {
         let elf = Elf::new(0, 0);
-        let mut locations = vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, -1), Point::new(-1, 0)];
+        let mut locations = vec![
+            Point2::new(0, 0),
+            Point2::new(0, -1),
+            Point2::new(0, 1),
+            Point2::new(-1, 0),
+        ];
         locations.sort();
 
-        assert_eq!(elf.propose_movement(&locations, 0), Some(Point::EAST));
+        assert_eq!(elf.propose_movement(&locations, 0), Some(Point2::new(1, 0)));
     }
 }
@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day25, String, usize);
+
+fn main() -> R<()> {
+    run::<Day25>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<String> {
@@ -54,6 +48,7 @@ fn decimal_to_snafu(x: isize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day25);
     const SAMPLE1: &str = r#"1=-0-2
 12111
 2=0=
@@ -1,32 +1,9 @@
 use common::*;
-use std::ops::Range;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
+solution!(Day05, usize, usize);
 
-#[derive(Default, Debug)]
-struct Mapping(Vec<(Range<usize>, usize)>);
-
-impl Mapping {
-    fn get(&self, key: usize) -> usize {
-        // If the mapping exist in any range, map it to the output range
-        for (range, dest) in &self.0 {
-            if range.contains(&key) {
-                return key - range.start + dest;
-            }
-        }
-        // If mapping didn't exist return the key as it is a 1:1 mapping
-        key
-    }
+fn main() -> R<()> {
+    run::<Day05>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -47,46 +24,35 @@ fn part1(input: &str) -> R<usize> {
         .unwrap())
 }
 
-fn parse_mappings(lines: std::str::Lines<'_>) -> Vec<Mapping> {
+fn parse_mappings(lines: std::str::Lines<'_>) -> Vec<RangeMap<usize>> {
     let mut mappings = vec![];
-    let mut current_mapping = Mapping::default();
+    let mut current_mapping = RangeMap::default();
     for line in lines {
         if line.is_empty() {
             continue;
         }
         if line.contains("map") {
             mappings.push(current_mapping);
-            current_mapping = Mapping::default();
+            current_mapping = RangeMap::default();
             continue;
         }
         let numbers = line.split(' ').map(|x| x.parse::<usize>().unwrap()).collect::<Vec<_>>();
         let (dest, source, len) = (numbers[0], numbers[1], numbers[2]);
-        let input_range = source..source + len;
-        let map = (input_range, dest);
-        current_mapping.0.push(map)
+        current_mapping.insert(source..source + len, dest);
     }
     mappings.push(current_mapping);
     mappings
 }
 
-//  Takes about ~3 seconds to run in release on 7950x due to the large number of seeds to check
 fn part2(input: &str) -> R<usize> {
     let mut lines = input.lines();
     // First line is seeds
     let seeds = parse_seeds(&mut lines);
-    // Convert the pairs of numbers into ranges and flat_map them to make a new iterator over all numbers. This is the only change needed for pt2
-    let seeds = seeds.par_chunks(2).flat_map(|x| x[0]..x[0] + x[1]);
+    // The pairs of numbers are ranges, move the whole ranges through each mapping instead of every seed
+    let seeds = seeds.chunks(2).map(|x| x[0]..x[0] + x[1]).collect::<IntervalSet<_>>();
     let mappings = parse_mappings(lines);
-    Ok(seeds
-        .map(|seed| {
-            let mut seed = seed;
-            for mapping in &mappings {
-                seed = mapping.get(seed);
-            }
-            seed
-        })
-        .min()
-        .unwrap())
+    let locations = mappings.iter().fold(seeds, |seeds, mapping| mapping.map_set(&seeds));
+    Ok(locations.min().unwrap())
 }
 
 fn parse_seeds(lines: &mut std::str::Lines<'_>) -> Vec<usize> {
@@ -106,6 +72,7 @@ fn parse_seeds(lines: &mut std::str::Lines<'_>) -> Vec<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day05);
     const SAMPLE1: &str = r#"seeds: 79 14 55 13
 
 seed-to-soil map:
@@ -4,16 +4,10 @@ use itertools::Itertools;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day07, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day07>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -230,6 +224,7 @@ fn part2(input: &str) -> R<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day07);
     const SAMPLE1: &str = r#"32T3K 765
 T55J5 684
 KK677 28
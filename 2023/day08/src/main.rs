@@ -1,18 +1,11 @@
 use std::collections::HashMap;
 
 use common::*;
-use num::integer::lcm;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day08, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day08>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> R<usize> {
@@ -66,7 +59,8 @@ fn part2(input: &str) -> R<usize> {
         })
         .collect::<Vec<_>>();
 
-    Ok(least_common_multiple_many(&instructions_followed))
+    // Each ghost loops back to its Z node on the same period, so they all line up at the lcm
+    Ok(lcm_many(&instructions_followed).expect("lcm fits in usize"))
 }
 
 fn parse_inputs(input: &str) -> (std::iter::Cycle<std::str::Chars<'_>>, HashMap<&str, (&str, &str)>) {
@@ -83,20 +77,10 @@ fn parse_inputs(input: &str) -> (std::iter::Cycle<std::str::Chars<'_>>, HashMap<
     (instructions, map)
 }
 
-// Finds the lcm of multiple numbers using recursion
-fn least_common_multiple_many(numbers: &[usize]) -> usize {
-    if numbers.len() == 2 {
-        lcm(numbers[0], numbers[1])
-    } else {
-        let a = numbers[0];
-        let b = least_common_multiple_many(&numbers[1..]);
-        lcm(a, b)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day08);
     const SAMPLE1: &str = r#"RL
 
 AAA = (BBB, CCC)
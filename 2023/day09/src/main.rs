@@ -2,16 +2,10 @@ use std::collections::VecDeque;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day09, isize, isize);
+
+fn main() -> R<()> {
+    run::<Day09>(&read_input_file_for_project_as_string!())
 }
 
 // Carry a vec<vec<isize>> and make a new vec for each round until it is all 0s.
@@ -74,6 +68,7 @@ fn part2(input: &str) -> R<isize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day09);
     const SAMPLE1: &str = r#"0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45"#;
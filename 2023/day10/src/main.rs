@@ -1,108 +1,69 @@
 /// This one is messy pt2 got me good
-use std::collections::BTreeSet;
-
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day10, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day10>(&read_input_file_for_project_as_string!())
 }
 
-fn determine_new_direction(previous_dir: Direction, c: char) -> Option<Direction> {
+fn determine_new_direction(previous_dir: Dir4, c: char) -> Option<Dir4> {
     match (previous_dir, c) {
-        (Direction::North, '|') => Some(Direction::North),
-        (Direction::North, '7') => Some(Direction::West),
-        (Direction::North, 'F') => Some(Direction::East),
-        (Direction::East, '-') => Some(Direction::East),
-        (Direction::East, 'J') => Some(Direction::North),
-        (Direction::East, '7') => Some(Direction::South),
-        (Direction::South, '|') => Some(Direction::South),
-        (Direction::South, 'L') => Some(Direction::East),
-        (Direction::South, 'J') => Some(Direction::West),
-        (Direction::West, '-') => Some(Direction::West),
-        (Direction::West, 'F') => Some(Direction::South),
-        (Direction::West, 'L') => Some(Direction::North),
+        (Dir4::Up, '|') => Some(Dir4::Up),
+        (Dir4::Up, '7') => Some(Dir4::Left),
+        (Dir4::Up, 'F') => Some(Dir4::Right),
+        (Dir4::Right, '-') => Some(Dir4::Right),
+        (Dir4::Right, 'J') => Some(Dir4::Up),
+        (Dir4::Right, '7') => Some(Dir4::Down),
+        (Dir4::Down, '|') => Some(Dir4::Down),
+        (Dir4::Down, 'L') => Some(Dir4::Right),
+        (Dir4::Down, 'J') => Some(Dir4::Left),
+        (Dir4::Left, '-') => Some(Dir4::Left),
+        (Dir4::Left, 'F') => Some(Dir4::Down),
+        (Dir4::Left, 'L') => Some(Dir4::Up),
 
         _ => None,
     }
 }
 
-fn move_forward(dir: Direction, coord: (usize, usize)) -> (usize, usize) {
-    let (row, col) = coord;
-    match dir {
-        Direction::North => {
-            // Handle overflows
-            if row == 0 {
-                (row, col)
-            } else {
-                (row - 1, col)
-            }
-        }
-        Direction::South => (row + 1, col),
-        Direction::East => (row, col + 1),
-        Direction::West => {
-            if col == 0 {
-                (row, col)
-            } else {
-                (row, col - 1)
-            }
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-fn coord_from_index(index: usize, max_cols: usize) -> (usize, usize) {
-    (index / max_cols, index % max_cols)
+/// The position one step away and its tile, None when it is off the grid
+fn step(grid: &Grid, position: Point2<usize>, dir: Dir4) -> Option<(Point2<usize>, char)> {
+    let next = position.checked_step(dir)?;
+    Some((next, *grid.at(next.signed())?))
 }
 
-fn index_from_coord(row: usize, col: usize, max_cols: usize) -> usize {
-    row * max_cols + col
+/// Directions that leave the start through a pipe connected to it
+fn start_directions(grid: &Grid, start: Point2<usize>) -> impl Iterator<Item = Dir4> + '_ {
+    [Dir4::Up, Dir4::Down, Dir4::Right, Dir4::Left]
+        .into_iter()
+        .filter(move |&dir| {
+            step(grid, start, dir).is_some_and(|(_, tile)| determine_new_direction(dir, tile).is_some())
+        })
 }
 
-trait Day10 {
-    fn adjancent_coord(&self) -> [(usize, usize); 4];
+fn find_start(grid: &Grid) -> R<Point2<usize>> {
+    let (row, col) = grid
+        .find_char('S')
+        .ok_or_else(|| AdventOfCodeError::new("No start in the grid"))?;
+    Ok(Point2::new(col, row))
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
-    let max_cols = input.lines().next().unwrap().len();
-    let input = input.replace('\n', "");
-
-    let start = coord_from_index(input.find('S').unwrap(), max_cols);
-    let mut current_directions = vec![];
-    for direction in [Direction::North, Direction::South, Direction::East, Direction::West] {
-        let adjacent_spot = move_forward(direction, start);
-        let adjacent_char =
-            input.as_bytes()[index_from_coord(adjacent_spot.0, adjacent_spot.1, max_cols) as usize] as char;
-        if (determine_new_direction(direction, adjacent_char)).is_some() {
-            current_directions.push(direction);
-        }
-    }
+    let grid = Grid::new(input);
+    let start = find_start(&grid)?;
+    let [first, second] = start_directions(&grid, start).take(2).collect_vec()[..] else {
+        Err(AdventOfCodeError::new("The start connects to less than two pipes"))?
+    };
+    let mut current_directions = [first, second];
     let mut current_positions = [start, start];
     let mut solution = 0;
     while solution == 0 || current_positions[0] != current_positions[1] {
         for i in 0..2 {
-            current_positions[i] = move_forward(current_directions[i], current_positions[i]);
-            let next_char = input.as_bytes()
-                [index_from_coord(current_positions[i].0, current_positions[i].1, max_cols) as usize]
-                as char;
-            let new_direction = determine_new_direction(current_directions[i], next_char);
-            if let Some(x) = new_direction {
-                current_directions[i] = x;
-            }
+            let (next, next_char) = step(&grid, current_positions[i], current_directions[i])
+                .ok_or_else(|| AdventOfCodeError::new("The loop leaves the grid"))?;
+            current_positions[i] = next;
+            current_directions[i] = determine_new_direction(current_directions[i], next_char)
+                .ok_or_else(|| AdventOfCodeError::new("The loop is broken"))?;
         }
         solution += 1
     }
@@ -110,69 +71,33 @@ fn part1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let max_cols = input.lines().next().unwrap().len();
-    let input = input.replace('\n', "");
-
-    let start = coord_from_index(input.find('S').unwrap(), max_cols);
-    let mut current_directions = vec![];
-    for direction in [Direction::North, Direction::South, Direction::East, Direction::West] {
-        let adjacent_spot = move_forward(direction, start);
-        let adjacent_char =
-            input.as_bytes()[index_from_coord(adjacent_spot.0, adjacent_spot.1, max_cols) as usize] as char;
-        if (determine_new_direction(direction, adjacent_char)).is_some() {
-            current_directions.push(direction);
-        }
-    }
-    let mut current_positions = [start, start];
-    let mut loop_points = BTreeSet::new();
-    while loop_points.is_empty() || current_positions[0] != current_positions[1] {
-        for i in 0..2 {
-            loop_points.insert(current_positions[i]);
-            current_positions[i] = move_forward(current_directions[i], current_positions[i]);
-            let next_char = input.as_bytes()
-                [index_from_coord(current_positions[i].0, current_positions[i].1, max_cols) as usize]
-                as char;
-            let new_direction = determine_new_direction(current_directions[i], next_char);
-            if let Some(x) = new_direction {
-                current_directions[i] = x;
-            }
-        }
-    }
-    loop_points.insert(current_positions[0]);
-    let mut inside_points = vec![];
-    let mut inside = false;
-    for (i, c) in input.chars().enumerate() {
-        let (row, col) = coord_from_index(i, max_cols);
-        if col == 0 {
-            // Start of the row, we are not inside
-            println!();
-            inside = false;
-        }
-        // If this point is on the loop it is not inside it
-        if loop_points.contains(&(row, col)) {
-            print!("{c}");
-            // If F | or 7 is found, invert the inside. I consider these "Moving Up" tiles. Should
-            // also work for "Moving Down" tiles L | J
-            // HACK: For my input 'S' is a '|'
-            if let 'F' | '|' | '7' | 'S' = c {
-                inside = !inside
-            }
-        } else if inside {
-            // If we are inside the loop and this point is not part of the loop, it is inside
-            print!("I");
-            inside_points.push((row, col));
-        } else {
-            // Otherwise is is an outside point
-            print!("O");
+    let grid = Grid::new(input);
+    let start = find_start(&grid)?;
+    // Leave the start through any pipe connected to it and follow the loop back around
+    let mut direction = start_directions(&grid, start)
+        .next()
+        .ok_or_else(|| AdventOfCodeError::new("The start connects to no pipes"))?;
+    let mut position = start;
+    let mut vertices = vec![];
+    loop {
+        let (next, tile) =
+            step(&grid, position, direction).ok_or_else(|| AdventOfCodeError::new("The loop leaves the grid"))?;
+        position = next;
+        vertices.push(Point2::new(position.x as i64, position.y as i64));
+        if position == start {
+            break;
         }
+        direction =
+            determine_new_direction(direction, tile).ok_or_else(|| AdventOfCodeError::new("The loop is broken"))?;
     }
-    println!();
-    Ok(inside_points.len())
+    // Every pipe in the loop is a corner of the polygon, so the enclosed tiles are the interior lattice points
+    Ok(interior_points(&vertices) as usize)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day10);
     const SAMPLE1: &str = r#".....
 .S-7.
 .|.|.
@@ -2,35 +2,17 @@ use std::collections::BTreeSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
+solution!(Day11, isize, isize);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    row: isize,
-    col: isize,
+fn main() -> R<()> {
+    run::<Day11>(&read_input_file_for_project_as_string!())
 }
 
-impl Point {
-    fn from_index(index: usize, width: isize) -> Point {
-        Point {
-            row: (index as isize / width),
-            col: (index as isize % width),
-        }
-    }
-}
+/// x is the col and y the row
+type Point = Point2<isize>;
 
-fn manhatten_distance(a: &Point, b: &Point) -> isize {
-    (a.row - b.row).abs() + (a.col - b.col).abs()
+fn from_index(index: usize, width: isize) -> Point {
+    Point::new(index as isize % width, index as isize / width)
 }
 
 fn part1(input: &str) -> anyhow::Result<isize> {
@@ -39,7 +21,7 @@ fn part1(input: &str) -> anyhow::Result<isize> {
     Ok(galaxies
         .into_iter()
         .tuple_combinations()
-        .map(|(a, b)| manhatten_distance(&a, &b))
+        .map(|(a, b)| a.manhattan(&b))
         .sum())
 }
 
@@ -53,15 +35,15 @@ fn part2(input: &str) -> anyhow::Result<isize> {
     Ok(galaxies
         .into_iter()
         .tuple_combinations()
-        .map(|(a, b)| manhatten_distance(&a, &b))
+        .map(|(a, b)| a.manhattan(&b))
         .sum())
 }
 
 fn expand_space(galaxies: &mut Vec<Point>, empty_rows: Vec<isize>, empty_cols: Vec<isize>, empty_space_size: isize) {
     let multiple = empty_space_size - 1; // Each empty space is already 1 so increase it by size -1
     for galaxy in galaxies {
-        galaxy.row += multiple * empty_rows.iter().filter(|x| **x < galaxy.row).count() as isize;
-        galaxy.col += multiple * empty_cols.iter().filter(|x| **x < galaxy.col).count() as isize;
+        galaxy.y += multiple * empty_rows.iter().filter(|x| **x < galaxy.y).count() as isize;
+        galaxy.x += multiple * empty_cols.iter().filter(|x| **x < galaxy.x).count() as isize;
     }
 }
 
@@ -71,10 +53,10 @@ fn parse_input(input: &str) -> (Vec<Point>, Vec<isize>, Vec<isize>) {
     let flat_input = input.replace('\n', "");
     let galaxies = flat_input
         .match_indices('#')
-        .map(|(i, _)| Point::from_index(i, max_col))
+        .map(|(i, _)| from_index(i, max_col))
         .collect::<Vec<Point>>();
-    let populated_rows = galaxies.iter().map(|p| p.row).collect::<BTreeSet<_>>();
-    let populated_col = galaxies.iter().map(|p| p.col).collect::<BTreeSet<_>>();
+    let populated_rows = galaxies.iter().map(|p| p.y).collect::<BTreeSet<_>>();
+    let populated_col = galaxies.iter().map(|p| p.x).collect::<BTreeSet<_>>();
     let empty_rows = (0..max_row).filter(|r| !populated_rows.contains(r)).collect_vec();
     let empty_cols = (0..max_col).filter(|c| !populated_col.contains(c)).collect_vec();
     (galaxies, empty_rows, empty_cols)
@@ -83,6 +65,7 @@ fn parse_input(input: &str) -> (Vec<Point>, Vec<isize>, Vec<isize>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day11);
     const SAMPLE1: &str = r#"...#......
 .......#..
 #.........
@@ -1,111 +1,50 @@
-use common::winnow::stream::AsChar;
-use std::collections::BTreeMap;
-use std::io::Write;
-
-/// Make a .dot file to be render with graphviz, a bit hacky
-pub fn create_visual_graph(map: BTreeMap<(usize, usize, usize), usize>, puzzle: String, key: Vec<usize>) {
-    let mut dot_file = std::fs::File::create("day12.dot").unwrap();
-    let mut file_contents = String::new();
-
-    file_contents += "strict digraph {\n";
-    file_contents += r#"rankdir = "LR";"#;
-    for ((puzzle_index, key_index, current_count), solution) in &map {
-        if *solution == 0 {
-            continue;
-        }
-        let char = puzzle.as_bytes().get(*puzzle_index).unwrap_or(&b'X').as_char();
-
-        file_contents += format!(
-            r#"_{}_{}_{} [shape="record", label= "{}|solutions:{}"];{}"#,
-            puzzle_index, key_index, current_count, char, solution, '\n'
-        )
-        .as_str();
-        if let '#' | '?' = char {
-            if let Some(s2) = map.get(&(puzzle_index + 1, *key_index, current_count + 1)) {
-                file_contents += format!(
-                    r##"_{}_{}_{} -> _{}_{}_{} [label = "#"];{}"##,
-                    puzzle_index,
-                    key_index,
-                    current_count,
-                    puzzle_index + 1,
-                    key_index,
-                    current_count + 1,
-                    '\n'
-                )
-                .as_str();
-                if *s2 == 0 {
-                    file_contents += format!(
-                        r#"_{}_{}_{} [shape="record", color="red", label= "{}|solutions:{}"];{}"#,
-                        puzzle_index + 1,
-                        key_index,
-                        current_count + 1,
-                        char,
-                        s2,
-                        '\n'
-                    )
-                    .as_str()
-                }
-            }
-        }
-        if let '.' | '?' = char {
-            if *current_count != 0 {
-                if let Some(a) = key.get(*key_index) {
-                    if *current_count == *a {
-                        if let Some(s2) = map.get(&(puzzle_index + 1, key_index + 1, 0)) {
-                            file_contents += format!(
-                                r##"_{}_{}_{} -> _{}_{}_{} [label = "."];{}"##,
-                                puzzle_index,
-                                key_index,
-                                current_count,
-                                puzzle_index + 1,
-                                key_index + 1,
-                                0,
-                                '\n'
-                            )
-                            .as_str();
-                            if *s2 == 0 {
-                                file_contents += format!(
-                                    r#"_{}_{}_{} [shape="record", color="red",label= "{}|solutions:{}"];{}"#,
-                                    puzzle_index + 1,
-                                    key_index + 1,
-                                    0,
-                                    char,
-                                    s2,
-                                    '\n'
-                                )
-                                .as_str()
-                            }
-                        }
-                    }
-                }
-            } else if let Some(s2) = map.get(&(puzzle_index + 1, *key_index, *current_count)) {
-                file_contents += format!(
-                    r##"_{}_{}_{} -> _{}_{}_{} [label = "."];{}"##,
-                    puzzle_index,
-                    key_index,
-                    current_count,
-                    puzzle_index + 1,
-                    key_index,
-                    current_count,
-                    '\n'
-                )
-                .as_str();
-                if *s2 == 0 {
-                    file_contents += format!(
-                        r#"_{}_{}_{} [shape="record", color="red",label= "{}|solutions:{}"];{}"#,
-                        puzzle_index + 1,
-                        key_index,
-                        current_count,
-                        char,
-                        s2,
-                        '\n'
-                    )
-                    .as_str()
-                }
-            }
-        }
-    }
-    file_contents += "}\n";
-    dot_file.write_all(file_contents.as_bytes()).unwrap();
-
-}
+use common::{winnow::stream::AsChar, DotGraph, R};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+/// Make a .dot file to be rendered with graphviz, dead ends with no solutions are red
+pub fn create_visual_graph<P>(
+    map: &BTreeMap<(usize, usize, usize), usize>,
+    puzzle: &str,
+    key: &[usize],
+    path: P,
+) -> R<()>
+where
+    P: AsRef<Path>,
+{
+    let char_at = |puzzle_index: usize| puzzle.as_bytes().get(puzzle_index).unwrap_or(&b'X').as_char();
+    let next = |&(puzzle_index, key_index, current_count): &(usize, usize, usize)| {
+        let mut edges = vec![];
+        let char = char_at(puzzle_index);
+        if let '#' | '?' = char {
+            edges.push(((puzzle_index + 1, key_index, current_count + 1), Some("#".to_string())));
+        }
+        if let '.' | '?' = char {
+            if current_count == 0 {
+                edges.push(((puzzle_index + 1, key_index, 0), Some(".".to_string())));
+            } else if key.get(key_index) == Some(&current_count) {
+                edges.push(((puzzle_index + 1, key_index + 1, 0), Some(".".to_string())));
+            }
+        }
+        edges
+    };
+    // Only entries with solutions are drawn, along with the dead ends they lead to in red
+    let dead_ends = map
+        .iter()
+        .filter(|(_, &solution)| solution != 0)
+        .flat_map(|(node, _)| next(node))
+        .map(|(node, _)| node)
+        .filter(|node| map.get(node) == Some(&0))
+        .collect::<BTreeSet<_>>();
+    let mut graph = DotGraph::from_memo(
+        map.iter()
+            .filter(|(node, &solution)| solution != 0 || dead_ends.contains(node)),
+        |&(puzzle_index, _, _), solution| format!("{}|solutions:{}", char_at(puzzle_index), solution),
+        |node, &solution| if solution == 0 { vec![] } else { next(node) },
+    )
+    .with_node_attribute("shape", "record");
+    graph.highlight_nodes(dead_ends);
+    graph.save(path)
+}
@@ -2,19 +2,31 @@ use std::collections::BTreeMap;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1(original algorithm): {}", part1(&input, true).unwrap());
+/// Part 1 uses the same memoised search as part 2, the original brute force is checked against it in the tests
+struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        Ok(input)
     }
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1(new algorithm): {}", part1(&input, false).unwrap());
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        part1(input, false)
     }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
+
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        part2(input)
+    }
+}
+
+fn main() -> R<()> {
+    run::<Day12>(&read_input_file_for_project_as_string!())?;
+    if !visualizing() {
+        return Ok(());
     }
     // Make a graphical ouput to explain the solution
     let input = "??#?###?????? 1,5,2"; // This is the input used to generate the graph
@@ -31,9 +43,11 @@ fn main() {
         .split(',')
         .map(|x| x.parse::<usize>().unwrap())
         .collect_vec();
-    let mut map = BTreeMap::new();
-    _ = get_solutions(&mut map, &puzzle, &key, 0, 0, 0);
-    day12::create_visual_graph(map, puzzle, key);
+    let mut memo = Memo::new();
+    _ = get_solutions(&mut memo, &puzzle, &key, 0, 0, 0);
+    // Sorted so the nodes come out in the same order every run
+    let map = memo.iter().map(|(&k, &v)| (k, v)).collect::<BTreeMap<_, _>>();
+    day12::create_visual_graph(&map, &puzzle, &key, "day12.dot")
 }
 
 fn part1(input: &str, part1_algo: bool) -> anyhow::Result<usize> {
@@ -134,7 +148,7 @@ fn solve_line2(input: &str) -> usize {
         .split(',')
         .map(|x| x.parse::<usize>().unwrap())
         .collect_vec();
-    let mut map = BTreeMap::new();
+    let mut map = Memo::new();
     get_solutions(&mut map, &puzzle, &key, 0, 0, 0)
 }
 
@@ -142,7 +156,7 @@ fn solve_line2(input: &str) -> usize {
 /// a single path, but it diverges if a '?' is found. If the function finds a similar state, it will
 /// use the previously calculated value.
 ///
-/// `map` HashMap of solved locations
+/// `map` memo of solved locations
 /// `puzzle` the string input of #/./?
 /// `key` the puzzle key ex: `[2,2]`
 /// `puzzle_index` the index into the puzzle
@@ -176,24 +190,20 @@ fn solve_line2(input: &str) -> usize {
 /// to have a solution so it exists early. Similar optimization exists when key_index > key.len()
 /// ```
 pub(crate) fn get_solutions(
-    map: &mut BTreeMap<(usize, usize, usize), usize>,
+    map: &mut Memo<(usize, usize, usize), usize>,
     puzzle: &str,
     key: &[usize],
     puzzle_index: usize,
     key_index: usize,
     current_count: usize,
 ) -> usize {
-    if map.contains_key(&(puzzle_index, key_index, current_count)) {
-        *map.get(&(puzzle_index, key_index, current_count)).unwrap()
-    } else {
+    map.get_or_insert_with((puzzle_index, key_index, current_count), |map| {
         if puzzle_index == puzzle.len() {
             // If we are at the last character, the key index is at the end and current count is 0, we
             // found a solution
             if key_index == key.len() && current_count == 0 {
-                map.insert((puzzle_index, key_index, current_count), 1);
                 return 1;
             } else {
-                map.insert((puzzle_index, key_index, current_count), 0);
                 // Last index, no solution return 0
                 return 0;
             }
@@ -246,9 +256,8 @@ pub(crate) fn get_solutions(
             }
             _ => 0,
         };
-        map.insert((puzzle_index, key_index, current_count), solution);
         solution
-    }
+    })
 }
 
 fn unfold_line(input: &str) -> String {
@@ -261,6 +270,7 @@ fn unfold_line(input: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day12);
     const SAMPLE1: &str = r#"???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
@@ -323,6 +333,57 @@ mod tests {
     fn p2_test() {
         assert_eq!(part2(SAMPLE1).unwrap(), 525152);
     }
+
+    /// Rows of springs short enough for the brute force, each with 1 to 3 groups
+    struct SpringRows {
+        rows: usize,
+        max_len: usize,
+    }
+
+    impl InputGenerator for SpringRows {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            (0..self.rows)
+                .map(|_| {
+                    let springs: String = (0..rng.gen_range(1..=self.max_len))
+                        .map(|_| ['.', '#', '?'][rng.gen_range(0..3)])
+                        .collect();
+                    let key = (0..rng.gen_range(1..4)).map(|_| rng.gen_range(1..4)).join(",");
+                    format!("{springs} {key}")
+                })
+                .join("\n")
+        }
+    }
+
+    /// Rows left out, then single springs left out of each row so a failure shrinks to the shortest row
+    fn shrink_springs(input: &str) -> Vec<String> {
+        let rows = input.lines().collect_vec();
+        let mut smaller = shrink_lines(input);
+        for (index, row) in rows.iter().enumerate() {
+            let (springs, key) = row.split_once(' ').unwrap();
+            if springs.len() < 2 {
+                continue;
+            }
+            for i in 0..springs.len() {
+                let mut rows = rows.iter().map(|row| row.to_string()).collect_vec();
+                rows[index] = format!("{}{} {key}", &springs[..i], &springs[i + 1..]);
+                smaller.push(rows.join("\n"));
+            }
+        }
+        smaller
+    }
+
+    #[test]
+    fn brute_force_difftest() {
+        let rows = SpringRows { rows: 5, max_len: 11 };
+        DiffTest::for_generator(
+            |input| part1(input, true).unwrap(),
+            |input| part1(input, false).unwrap(),
+            rows,
+        )
+        .with_shrink(|input| shrink_springs(input))
+        .run()
+        .unwrap();
+    }
     #[test]
     fn p2_testl1() {
         let a = unfold_line(SAMPLE1.lines().next().unwrap());
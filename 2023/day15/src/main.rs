@@ -1,15 +1,9 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day15, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day15>(&read_input_file_for_project_as_string!())
 }
 
 fn calc_hash(val: &str) -> usize {
@@ -66,6 +60,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day15);
     const SAMPLE1: &str = r#"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"#;
     #[test]
     fn p1_test() {
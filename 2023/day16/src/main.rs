@@ -2,126 +2,74 @@ use std::collections::HashSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-struct MirrorMaze {
-    inner: String,
-    rows: usize,
-    cols: usize,
-}
+solution!(Day16, usize, usize);
 
-impl MirrorMaze {
-    fn new(input: &str) -> Self {
-        let rows = input.lines().count();
-        let cols = input.lines().next().unwrap().chars().count();
-        let inner = input.replace('\n', "");
-        Self { inner, rows, cols }
-    }
-}
-
-impl MirrorMaze {
-    #[inline]
-    fn index(&self, row: isize, col: isize) -> Option<usize> {
-        if row < 0 || col < 0 || row >= self.rows as isize || col >= self.cols as isize {
-            None
-        } else {
-            let index = (row * self.cols as isize + col) as usize;
-            debug_assert!(index < self.inner.len(), "{},r{},c{}", index, row, col);
-            Some(index)
-        }
-    }
-
-    #[inline]
-    fn get_char(&self, row: isize, col: isize) -> Option<char> {
-        self.index(row, col).map(|index| self.inner.as_bytes()[index] as char)
-    }
+fn main() -> R<()> {
+    run::<Day16>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
-    let maze = MirrorMaze::new(input);
+    let maze = Grid::new(input);
     let mut map = HashSet::new();
-    walk_maze(&maze, 0, 0, Direction::Right, &mut map);
+    walk_maze(&maze, 0, 0, Dir4::Right, &mut map);
     // Convert the hashset from row,col,direction to just row,col This will be the total number of unique tiles
     Ok(map.into_iter().map(|(r, c, _)| (r, c)).collect::<HashSet<_>>().len())
 }
 
 // Walk the maze recursively stopping if this node and direction was already hit
-fn walk_maze(maze: &MirrorMaze, row: isize, col: isize, dir: Direction, map: &mut HashSet<(isize, isize, Direction)>) {
+fn walk_maze(maze: &Grid, row: isize, col: isize, dir: Dir4, map: &mut HashSet<(isize, isize, Dir4)>) {
     if map.contains(&(row, col, dir)) {
         // Already been here
         return;
     }
-    if let Some(char) = maze.get_char(row, col) {
+    if let Some(char) = maze.get_char_signed(row, col) {
         // Mark that we have been here
         map.insert((row, col, dir));
         // This match statement covers all possible reflections
         match (char, dir) {
             // Keep going same direction
-            ('.', _) | ('-', Direction::Left | Direction::Right) | ('|', Direction::Up | Direction::Down) => {
-                let (next_row, next_col) = match dir {
-                    Direction::Up => (row - 1, col),
-                    Direction::Down => (row + 1, col),
-                    Direction::Left => (row, col - 1),
-                    Direction::Right => (row, col + 1),
-                };
-                walk_maze(maze, next_row, next_col, dir, map)
+            ('.', _) | ('-', Dir4::Left | Dir4::Right) | ('|', Dir4::Up | Dir4::Down) => {
+                let delta = dir.delta::<isize>();
+                walk_maze(maze, row + delta.y, col + delta.x, dir, map)
             }
             // Splitters
-            ('-', Direction::Up | Direction::Down) => {
-                walk_maze(maze, row, col + 1, Direction::Right, map);
-                walk_maze(maze, row, col - 1, Direction::Left, map);
+            ('-', Dir4::Up | Dir4::Down) => {
+                walk_maze(maze, row, col + 1, Dir4::Right, map);
+                walk_maze(maze, row, col - 1, Dir4::Left, map);
             }
-            ('|', Direction::Left | Direction::Right) => {
-                walk_maze(maze, row + 1, col, Direction::Down, map);
-                walk_maze(maze, row - 1, col, Direction::Up, map);
+            ('|', Dir4::Left | Dir4::Right) => {
+                walk_maze(maze, row + 1, col, Dir4::Down, map);
+                walk_maze(maze, row - 1, col, Dir4::Up, map);
             }
             // Reflections
-            ('/', Direction::Up) => walk_maze(maze, row, col + 1, Direction::Right, map),
-            ('/', Direction::Down) => walk_maze(maze, row, col - 1, Direction::Left, map),
-            ('/', Direction::Left) => walk_maze(maze, row + 1, col, Direction::Down, map),
-            ('/', Direction::Right) => walk_maze(maze, row - 1, col, Direction::Up, map),
-            ('\\', Direction::Up) => walk_maze(maze, row, col - 1, Direction::Left, map),
-            ('\\', Direction::Down) => walk_maze(maze, row, col + 1, Direction::Right, map),
-            ('\\', Direction::Left) => walk_maze(maze, row - 1, col, Direction::Up, map),
-            ('\\', Direction::Right) => walk_maze(maze, row + 1, col, Direction::Down, map),
+            ('/', Dir4::Up) => walk_maze(maze, row, col + 1, Dir4::Right, map),
+            ('/', Dir4::Down) => walk_maze(maze, row, col - 1, Dir4::Left, map),
+            ('/', Dir4::Left) => walk_maze(maze, row + 1, col, Dir4::Down, map),
+            ('/', Dir4::Right) => walk_maze(maze, row - 1, col, Dir4::Up, map),
+            ('\\', Dir4::Up) => walk_maze(maze, row, col - 1, Dir4::Left, map),
+            ('\\', Dir4::Down) => walk_maze(maze, row, col + 1, Dir4::Right, map),
+            ('\\', Dir4::Left) => walk_maze(maze, row - 1, col, Dir4::Up, map),
+            ('\\', Dir4::Right) => walk_maze(maze, row + 1, col, Dir4::Down, map),
             _ => unreachable!(),
         }
     }
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let maze = MirrorMaze::new(input);
+    let maze = Grid::new(input);
     // Determine all possible starting possitions and run maze for each of them in parallel finding
     // the max result
     let mut starting_conditions = vec![];
     for col in 0..maze.cols as isize {
         // Start at top row moving down for each column
-        starting_conditions.push((0, col, Direction::Down));
+        starting_conditions.push((0, col, Dir4::Down));
         // Bottom row each column moving up
-        starting_conditions.push((maze.rows as isize - 1, col, Direction::Up));
+        starting_conditions.push((maze.rows as isize - 1, col, Dir4::Up));
     }
     for row in 0..maze.rows as isize {
         // Left most column for reach row moving right
-        starting_conditions.push((row, 0, Direction::Right));
-        starting_conditions.push((row, maze.cols as isize - 1, Direction::Left));
+        starting_conditions.push((row, 0, Dir4::Right));
+        starting_conditions.push((row, maze.cols as isize - 1, Dir4::Left));
     }
     Ok(starting_conditions
         // Parallel not really required heres, changes execution from 250ms to 50ms. I assumed it
@@ -140,6 +88,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day16);
     const SAMPLE1: &str = r#".|...\....
 |.-.\.....
 .....|-...
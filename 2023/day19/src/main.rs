@@ -10,16 +10,10 @@ use common::{
     *,
 };
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day19, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day19>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -98,21 +92,14 @@ fn parse_comparison(input: &mut &str) -> PResult<Comparison> {
 }
 
 fn parse_part(input: &mut &str) -> PResult<Part> {
-    let mut part = Part::new();
-    // Get text between { }
-    let (_, mut inner) = ("{", take_until1("}")).parse_next(input)?;
-    // Parse the key,value separted by ,
-    let a: Vec<(char, u32)> = separated(.., parse_part_key_value, ",").parse_next(&mut inner)?;
-    for (key, value) in a {
-        part.insert(key, value as usize);
-    }
-
-    Ok(part)
+    // Parse the key=value pairs between { } separated by ,
+    let (_, pairs, _) = ('{', comma_list(parse_part_key_value), '}').parse_next(input)?;
+    Ok(pairs.into_iter().collect())
 }
 
-fn parse_part_key_value(a: &mut &str) -> PResult<(char, u32)> {
+fn parse_part_key_value(a: &mut &str) -> PResult<(char, usize)> {
     // Extract key,value from key=value
-    separated_pair(one_of(['x', 'm', 'a', 's']), "=", dec_uint).parse_next(a)
+    separated_pair(one_of(['x', 'm', 'a', 's']), "=", int).parse_next(a)
 }
 
 trait SolvePart {
@@ -162,38 +149,37 @@ impl SolvePart for Part {
 
 const START: &str = "in";
 fn part1(input: &str) -> anyhow::Result<usize> {
+    ratings(input, false)
+}
+
+/// Sum of the ratings of the accepted parts, when `remap` the workflows are split into the single if/else conditions
+/// used by part 2
+fn ratings(input: &str, remap: bool) -> anyhow::Result<usize> {
     let mut workflows = HashMap::new();
     let mut workflows_remap = HashMap::new();
-    let mut parts = vec![];
-    let mut empty_hit = false;
-    for mut line in &mut input.lines() {
-        if !empty_hit {
-            if line.is_empty() {
-                empty_hit = true;
-                continue;
-            }
-            let (key, value) = parse_workflow(&mut line).map_err(|e| anyhow::anyhow!(e))?;
-            let pairs = remap_pt2(key, &value, 0);
-            workflows.insert(key, value);
-            for (key, value) in pairs {
-                workflows_remap.insert(key, value);
-            }
-        } else {
-            let part = parse_part(&mut line).map_err(|e| anyhow::anyhow!(e))?;
-            parts.push(part);
+    let [workflow_lines, part_lines] = sections(input)[..] else {
+        Err(AdventOfCodeError::new(
+            "Expected workflows and parts separated by a blank line",
+        ))?
+    };
+    for (key, value) in parse_lines(parse_workflow, workflow_lines)? {
+        let pairs = remap_pt2(key, &value, 0);
+        workflows.insert(key, value);
+        for (key, value) in pairs {
+            workflows_remap.insert(key, value);
         }
     }
+    let parts = parse_lines(parse_part, part_lines)?;
 
-    let ans = parts
-        .clone()
-        .into_iter()
-        .fold(0usize, |acc, part| acc + part.rating(&workflows));
-    let ans_remap = parts
-        .into_iter()
-        .fold(0usize, |acc, part| acc + part.rating2(&workflows_remap));
-    // Verify the same logic works for pt1 even with the remap
-    assert_eq!(ans, ans_remap);
-    Ok(ans)
+    if remap {
+        Ok(parts
+            .into_iter()
+            .fold(0usize, |acc, part| acc + part.rating2(&workflows_remap)))
+    } else {
+        Ok(parts
+            .into_iter()
+            .fold(0usize, |acc, part| acc + part.rating(&workflows)))
+    }
 }
 
 #[derive(Debug)]
@@ -216,41 +202,33 @@ impl Part2Condition {
 
 fn part2(input: &str) -> anyhow::Result<usize> {
     let mut workflows = HashMap::new();
-    for mut line in &mut input.lines() {
-        if line.is_empty() {
-            break;
-        }
-        let (key, value) = parse_workflow(&mut line).map_err(|e| anyhow::anyhow!(e))?;
+    let workflow_lines = sections(input).into_iter().next().unwrap_or_default();
+    for (key, value) in parse_lines(parse_workflow, workflow_lines)? {
         // Rework inputs so each map only has a single if/else
         let pairs = remap_pt2(key, &value, 0);
         for (key, value) in pairs {
             workflows.insert(key, value);
         }
     }
-    let start_limits = HashMap::from([('x', (1, 4000)), ('m', (1, 4000)), ('a', (1, 4000)), ('s', (1, 4000))]);
+    let start_limits: Limits = ['x', 'm', 'a', 's']
+        .into_iter()
+        .map(|key| (key, [1..=MAX_RATING].into_iter().collect::<IntervalSet<_>>()))
+        .collect();
     let results = solve_pt2(&workflows, start_limits, START.to_string());
-    let mut ans = 0;
     // For each limit calculate product of the valid range of values and sum them up
-    for result in results {
-        let mut values = 1usize;
-        for x in ['x', 'm', 'a', 's'] {
-            if let Some((min, max)) = result.get(&x) {
-                values *= max - min + 1;
-            }
-        }
-        ans += values;
-    }
-    Ok(ans)
+    Ok(results
+        .into_iter()
+        .map(|result| result.values().map(IntervalSet::len).product::<usize>())
+        .sum())
 }
 
+const MAX_RATING: usize = 4000;
+type Limits = HashMap<char, IntervalSet<usize>>;
+
 /// The idea here is to walk all decisions trees because the hashmap now forms a binary tree. At
-/// each step we calculate the min and max for each key. If the tree ends ins "A" we return those
+/// each step the values of the key are split on the condition. If the tree ends ins "A" we return those
 /// limits. Otherwise empty is returned.
-fn solve_pt2(
-    workflows: &HashMap<String, Part2Condition>,
-    current_limits: HashMap<char, (usize, usize)>,
-    key: String,
-) -> Vec<HashMap<char, (usize, usize)>> {
+fn solve_pt2(workflows: &HashMap<String, Part2Condition>, current_limits: Limits, key: String) -> Vec<Limits> {
     if key == "R" {
         vec![]
     } else if key == "A" {
@@ -259,16 +237,14 @@ fn solve_pt2(
         let workflow = &workflows[&key];
         let mut true_limit = current_limits.clone();
         let mut false_limit = current_limits;
+        let t = true_limit.get_mut(&workflow.key).unwrap();
+        let f = false_limit.get_mut(&workflow.key).unwrap();
         if workflow.operation == Operation::Greater {
-            let t = true_limit.get_mut(&workflow.key).unwrap();
-            t.0 = t.0.max(workflow.value + 1);
-            let f = false_limit.get_mut(&workflow.key).unwrap();
-            f.1 = f.1.min(workflow.value);
+            t.remove(0..=workflow.value);
+            f.remove(workflow.value + 1..=MAX_RATING);
         } else {
-            let t = true_limit.get_mut(&workflow.key).unwrap();
-            t.1 = t.1.min(workflow.value - 1);
-            let f = false_limit.get_mut(&workflow.key).unwrap();
-            f.0 = f.0.max(workflow.value);
+            t.remove(workflow.value..=MAX_RATING);
+            f.remove(0..workflow.value);
         }
         // Continue down the true and path, updating limits as we go
         let mut results = solve_pt2(workflows, true_limit, workflow.destination_if_true.clone());
@@ -313,6 +289,7 @@ fn remap_pt2(key: &str, values: &[Condition], depth: usize) -> Vec<(String, Part
 mod tests {
 
     use super::*;
+    answer_tests!(Day19);
     const SAMPLE1: &str = r#"px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
@@ -338,6 +315,69 @@ hdj{m>838:A,pv}
     fn p2_test() {
         assert_eq!(part2(SAMPLE1).unwrap(), 167409079868000);
     }
+    /// Workflows that only send parts to later workflows so they can't loop, then a few parts
+    fn random_input(rng: &mut rand::rngs::StdRng) -> String {
+        use rand::{seq::SliceRandom, Rng};
+        let names = (0..rng.gen_range(1..6))
+            .map(|i| {
+                if i == 0 {
+                    START.to_string()
+                } else {
+                    format!("w{}", (b'a' + i) as char)
+                }
+            })
+            .collect_vec();
+        let workflows = names.iter().enumerate().map(|(i, name)| {
+            let destinations = names[i + 1..]
+                .iter()
+                .map(String::as_str)
+                .chain(["A", "R"])
+                .collect_vec();
+            let conditions = (0..rng.gen_range(1..4))
+                .map(|_| {
+                    let key = ['x', 'm', 'a', 's'][rng.gen_range(0..4)];
+                    let operation = ['<', '>'][rng.gen_range(0..2)];
+                    format!(
+                        "{key}{operation}{}:{}",
+                        rng.gen_range(1..4000),
+                        destinations.choose(rng).unwrap()
+                    )
+                })
+                .collect_vec();
+            format!(
+                "{name}{{{},{}}}",
+                conditions.join(","),
+                destinations.choose(rng).unwrap()
+            )
+        });
+        let workflows = workflows.collect_vec().join("\n");
+        let parts = (0..rng.gen_range(1..6))
+            .map(|_| {
+                let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+                format!("{{x={x},m={m},a={a},s={s}}}")
+            })
+            .join("\n");
+        format!("{workflows}\n\n{parts}")
+    }
+    #[test]
+    fn remap_difftest() {
+        // Shrinking drops parts until the one the two ratings disagree on is left
+        DiffTest::new(
+            |input: &String| ratings(input, false).unwrap(),
+            |input| ratings(input, true).unwrap(),
+            random_input,
+        )
+        .with_shrink(|input| {
+            let (workflows, parts) = input.split_once("\n\n").unwrap();
+            shrink_lines(parts)
+                .into_iter()
+                .filter(|parts| !parts.is_empty())
+                .map(|parts| format!("{workflows}\n\n{parts}"))
+                .collect()
+        })
+        .run()
+        .unwrap();
+    }
     #[test]
     fn parse_comparison_test() {
         assert_eq!(
@@ -1,20 +1,14 @@
 use std::collections::HashSet;
 
 use common::{
-    winnow::{ascii::dec_uint, prelude::*, seq, PResult},
+    winnow::{prelude::*, seq, PResult},
     *,
 };
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day22, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day22>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,14 +34,7 @@ fn brick_from_str(input: &mut &str) -> PResult<Brick> {
 }
 
 fn position_from_str(input: &mut &str) -> PResult<Position> {
-    seq!(Position{
-        x:dec_uint,
-        _:',',
-        y:dec_uint,
-        _:',',
-        z:dec_uint
-    })
-    .parse_next(input)
+    point3.map(|Point3 { x, y, z }| Position { x, y, z }).parse_next(input)
 }
 
 fn bricks_fall_single_step(bricks: &[Brick]) -> (bool, Vec<Brick>) {
@@ -110,7 +97,7 @@ fn bricks_fall_single_step(bricks: &[Brick]) -> (bool, Vec<Brick>) {
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
-    let settled_bricks = parse_input_and_settle_bricks(input);
+    let settled_bricks = parse_input_and_settle_bricks(input)?;
     let mut solution = 0;
     // Go through every brick and remove one, run a single step of falling and see if any fell
     for i in 0..settled_bricks.len() {
@@ -124,14 +111,11 @@ fn part1(input: &str) -> anyhow::Result<usize> {
     Ok(solution)
 }
 
-fn parse_input_and_settle_bricks(input: &str) -> Vec<Brick> {
-    let mut initial_bricks = input
-        .lines()
-        .map(|mut line| brick_from_str(&mut line).unwrap())
-        .collect_vec();
+fn parse_input_and_settle_bricks(input: &str) -> R<Vec<Brick>> {
+    let mut initial_bricks = parse_lines(brick_from_str, input)?;
     // Sort so lowest Z is first for more efficient iterating
     initial_bricks.sort_by_key(|b| b.start.z);
-    settle_bricks(&initial_bricks)
+    Ok(settle_bricks(&initial_bricks))
 }
 
 fn settle_bricks(initial_bricks: &[Brick]) -> Vec<Brick> {
@@ -146,7 +130,7 @@ fn settle_bricks(initial_bricks: &[Brick]) -> Vec<Brick> {
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let settled_bricks = parse_input_and_settle_bricks(input);
+    let settled_bricks = parse_input_and_settle_bricks(input)?;
     let mut solution = 0;
     // Go through each and remove a brick, but this time run until settles. Then compare the
     // original copy with the new settled to determine how many changed
@@ -167,6 +151,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day22);
     const SAMPLE1: &str = r#"1,0,1~1,2,1
 0,0,2~2,0,2
 0,2,3~2,2,3
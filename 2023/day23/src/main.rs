@@ -1,70 +1,25 @@
-use std::{collections::HashMap, io::Write};
+use std::collections::HashMap;
 
 use common::{
-    petgraph::{algo::toposort, dot::Dot, Graph},
-    petgraph::{data::DataMap, prelude::*},
+    petgraph::{algo::toposort, EdgeType},
     *,
 };
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
+solution!(Day23, isize, isize);
 
-struct Input {
-    inner: Vec<char>,
-    rows: usize,
-    cols: usize,
+fn main() -> R<()> {
+    run::<Day23>(&read_input_file_for_project_as_string!())
 }
 
-impl Input {
-    fn new(input: &str) -> Self {
-        let rows = input.lines().count();
-        let cols = input.lines().next().unwrap().chars().count();
-        let inner = input.replace('\n', "").chars().collect();
-        Self { inner, rows, cols }
-    }
-
-    #[inline]
-    fn index(&self, row: isize, col: isize) -> Option<usize> {
-        if row < 0 || col < 0 || row >= self.rows as isize || col >= self.cols as isize {
-            None
-        } else {
-            let index = (row * self.cols as isize + col) as usize;
-            debug_assert!(index < self.inner.len(), "{},r{},c{}", index, row, col);
-            Some(index)
-        }
-    }
-
-    #[inline]
-    fn get_edge(&self, row: isize, col: isize) -> Option<char> {
-        self.index(row, col).map(|index| self.inner[index])
-    }
-}
 fn part1(input: &str) -> anyhow::Result<isize> {
-    let map = Input::new(input);
-    let mut graph = Graph::<(isize, isize), isize>::new();
-    // Determine start position
-    //  - Start at row0 col1
-    let start = graph.add_node((0, 1));
-    // Determine end position
-    graph.add_node((map.rows as isize - 1, map.cols as isize - 2));
-    // Build directed graph
-    // - Find all nodes
-    add_all_nodes(&mut graph, &map);
-    // - Walk the output for all nodes to find each edge
-    add_all_edges(&mut graph, &map);
-    let dot = Dot::new(&graph);
-    std::fs::File::create("day23pt1.dot")?.write_all(format!("{dot:?}").as_bytes())?;
+    let map = Grid::new(input);
+    // Directed graph of the junctions, slopes can only be walked down
+    let graph: DiGraph<_, _> = map.contract(&endpoints(&map), |&c| c != '#', slope);
+    let start = NodeIndex::new(0);
     // Use modified Djisktras to find longest path
     let mut distances = HashMap::new();
+    // Node each node was best reached from to trace the path back
+    let mut previous = HashMap::new();
     // https://www.geeksforgeeks.org/find-longest-path-directed-acyclic-graph/
     for b in graph.node_indices() {
         // But -negative inf for all nodes
@@ -75,170 +30,77 @@ fn part1(input: &str) -> anyhow::Result<isize> {
     // Go in topographical order and adjust the distance from each node
     for b in toposort(&graph, None).unwrap() {
         for edge in graph.edges_directed(b, Direction::Outgoing) {
-            if distances[&edge.target()] < distances[&edge.source()] + edge.weight() {
-                distances.insert(edge.target(), distances[&edge.source()] + edge.weight());
+            let distance = distances[&edge.source()] + *edge.weight() as isize;
+            if distances[&edge.target()] < distance {
+                distances.insert(edge.target(), distance);
+                previous.insert(edge.target(), edge.source());
             }
         }
     }
     // Max distance is the solution
-    Ok(*distances.values().max().unwrap())
-}
-
-fn add_all_nodes(graph: &mut Graph<(isize, isize), isize>, map: &Input) {
-    for r in 1..map.rows as isize - 1 {
-        for c in 1..map.cols as isize - 1 {
-            // Make sure current node is not a '#'
-            match map.get_edge(r, c) {
-                Some('#') | None => (),
-                Some(_) => {
-                    // If there are 3 or more paths (non '#' spaces) around this position then it is a node
-                    let paths = [
-                        map.get_edge(r - 1, c),
-                        map.get_edge(r + 1, c),
-                        map.get_edge(r, c - 1),
-                        map.get_edge(r, c + 1),
-                    ]
-                    .into_iter()
-                    .flatten()
-                    .filter(|x| *x != '#')
-                    .count();
-                    if paths >= 3 {
-                        graph.add_node((r, c));
-                    }
-                }
-            }
+    let (mut node, distance) = distances.iter().max_by_key(|(_, distance)| **distance).unwrap();
+    if visualizing() {
+        let mut path = vec![node.index()];
+        while let Some(prev) = previous.get(node) {
+            path.push(prev.index());
+            node = prev;
         }
+        path.reverse();
+        save_dot(&graph, &path, "day23pt1.dot")?;
     }
+    Ok(*distance)
 }
 
-fn add_all_edges(graph: &mut Graph<(isize, isize), isize>, map: &Input) {
-    for source in graph.node_indices() {
-        let (r, c) = graph.node_weight(source).unwrap();
-        let mut edges = vec![];
-        if let Some('>') = map.get_edge(*r, *c + 1) {
-            let (distance, edge) = walk_until_node((*r, *c + 1), (*r, *c), 1, graph, map);
-            let dest = graph
-                .node_indices()
-                .find(|x| graph.node_weight(*x).unwrap() == &edge)
-                .unwrap();
-            edges.push((source, dest, distance));
-        }
-        if let Some('v') | Some('.') = map.get_edge(*r + 1, *c) {
-            let (distance, edge) = walk_until_node((*r + 1, *c), (*r, *c), 1, graph, map);
-            let dest = graph
-                .node_indices()
-                .find(|x| graph.node_weight(*x).unwrap() == &edge)
-                .unwrap();
-            edges.push((source, dest, distance));
-        }
-        for (source, dest, distance) in edges {
-            graph.update_edge(source, dest, distance);
-        }
-    }
+/// Start at row 0 col 1 and end on the last row next to the right edge
+fn endpoints(map: &Grid) -> [Point2<usize>; 2] {
+    [Point2::new(1, 0), Point2::new(map.cols - 2, map.rows - 1)]
 }
 
-fn add_all_edges2(graph: &mut Graph<(isize, isize), isize>, map: &Input) {
-    for source in graph.node_indices() {
-        let (r, c) = graph.node_weight(source).unwrap();
-        let mut edges = vec![];
-        for (r2, c2) in [(*r + 1, *c), (*r - 1, *c), (*r, *c + 1), (*r, *c - 1)] {
-            if let Some('v') | Some('.') | Some('>') = map.get_edge(r2, c2) {
-                let (distance, edge) = walk_until_node((r2, c2), (*r, *c), 1, graph, map);
-                let dest = graph
-                    .node_indices()
-                    .find(|x| graph.node_weight(*x).unwrap() == &edge)
-                    .unwrap();
-                edges.push((source, dest, distance));
-            }
-        }
-        for (source, dest, distance) in edges {
-            graph.update_edge(source, dest, distance);
-        }
+/// Slopes can only be left in the direction they point
+fn slope(c: &char) -> Option<Dir4> {
+    match c {
+        '^' => Some(Dir4::Up),
+        '>' => Some(Dir4::Right),
+        'v' => Some(Dir4::Down),
+        '<' => Some(Dir4::Left),
+        _ => None,
     }
 }
 
-fn walk_until_node(
-    current: (isize, isize),
-    previous: (isize, isize),
-    distance: isize,
-    graph: &Graph<(isize, isize), isize>,
-    map: &Input,
-) -> (isize, (isize, isize)) {
-    if graph.raw_nodes().iter().any(|x| x.weight == current) {
-        (distance, current)
-    } else {
-        let (r, c) = current;
-        let next = [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
-            .into_iter()
-            // Remove previous entry
-            .filter(|x| *x != previous)
-            // Get the next item that has is not '#'
-            .filter(|(r, c)| match map.get_edge(*r, *c) {
-                Some('#') | None => false,
-                Some(_) => true,
-            })
-            // Should only be a single item left
-            .at_most_one()
-            .unwrap()
-            .unwrap();
-        walk_until_node(next, current, distance + 1, graph, map)
-    }
+/// Junctions labelled with their position and the steps between them, the longest path is highlighted
+fn save_dot<Ty>(graph: &Graph<Point2<usize>, usize, Ty>, path: &[usize], file: &str) -> R<()>
+where
+    Ty: EdgeType,
+{
+    let mut dot = DotGraph::from_petgraph(
+        graph,
+        |(_, point)| format!("{},{}", point.y, point.x),
+        |edge| Some(edge.weight().to_string()),
+    );
+    dot.highlight_path(path);
+    dot.save(file)
 }
 
 fn part2(input: &str) -> anyhow::Result<isize> {
-    let map = Input::new(input);
-    let mut graph = Graph::<(isize, isize), isize>::new();
-    // Determine start position
-    //  - Start at row0 col1
-    let start = graph.add_node((0, 1));
-    // Determine end position
-    let end = graph.add_node((map.rows as isize - 1, map.cols as isize - 2));
-    // Build directed graph
-    // - Find all nodes
-    add_all_nodes(&mut graph, &map);
-    // - Walk the output for all nodes to find each edge
-    add_all_edges2(&mut graph, &map);
-    let dot = Dot::new(&graph);
-    std::fs::File::create("day23pt2.dot")?.write_all(format!("{dot:?}").as_bytes())?;
-    let mut visited = HashMap::new();
-    let mut dist = HashMap::new();
-    for x in graph.node_indices() {
-        visited.insert(x, false);
-        dist.insert(x, 0);
-    }
-    longest_path(&graph, start, &mut visited, &mut dist, 0);
-    Ok(dist[&end])
-}
-
-fn longest_path(
-    graph: &Graph<(isize, isize), isize>,
-    current: NodeIndex,
-    visited: &mut HashMap<NodeIndex, bool>,
-    dist: &mut HashMap<NodeIndex, isize>,
-    current_dist: isize,
-) {
-    if visited[&current] {
-        return;
-    }
-    visited.insert(current, true);
-    if dist[&current] < current_dist {
-        dist.insert(current, current_dist);
-    }
-    for next in graph.neighbors(current) {
-        longest_path(
-            graph,
-            next,
-            visited,
-            dist,
-            current_dist + graph.edge_weight(graph.find_edge(current, next).unwrap()).unwrap(),
-        );
-    }
-    visited.insert(current, false);
+    let map = Grid::new(input);
+    // Slopes are ignored so every corridor can be walked either way
+    let graph: UnGraph<_, _> = map.contract(&endpoints(&map), |&c| c != '#', slope);
+    let (start, end) = (NodeIndex::new(0), NodeIndex::new(1));
+    let path = longest_path(&graph, start, end, true).ok_or(AdventOfCodeError::new("No path to the end"))?;
+    if visualizing() {
+        save_dot(
+            &graph,
+            &path.states.iter().map(|node| node.index()).collect_vec(),
+            "day23pt2.dot",
+        )?;
+    }
+    Ok(path.cost as isize)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day23);
     const SAMPLE1: &str = r#"#.#####################
 #.......#########...###
 #######.#########.#.###
@@ -1,28 +1,39 @@
 use common::{
-    winnow::{ascii::float, seq, PResult, Parser},
+    winnow::{
+        ascii::{float, space0},
+        PResult, Parser,
+    },
     *,
 };
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!(
-            "Part1: {}",
-            part1(
-                &input,
-                &Bounds {
-                    min: 200_000_000_000_000f64,
-                    max: 400_000_000_000_000f64,
-                }
-            )
-            .unwrap()
-        );
+struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        Ok(input)
     }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        part1(
+            input,
+            &Bounds {
+                min: 200_000_000_000_000f64,
+                max: 400_000_000_000_000f64,
+            },
+        )
     }
+
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        part2(input)
+    }
+}
+
+fn main() -> R<()> {
+    run::<Day24>(&read_input_file_for_project_as_string!())
 }
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct Hailstone {
@@ -79,20 +90,8 @@ impl Hailstone {
 }
 
 fn hailstone(input: &mut &str) -> PResult<Hailstone> {
-    seq!(Hailstone{
-        px:float,
-        _:", ",
-        py:float,
-        _:", ",
-        pz:float,
-        _: " @ ",
-        vx:float,
-        _: ", ",
-        vy:float,
-        _: ", ",
-        vz:float,
-    })
-    .parse_next(input)
+    let ((px, py, pz), _, (vx, vy, vz)) = (triple(float), (space0, '@', space0), triple(float)).parse_next(input)?;
+    Ok(Hailstone { px, py, pz, vx, vy, vz })
 }
 
 #[derive(Debug)]
@@ -102,9 +101,10 @@ struct Bounds {
 }
 
 fn part1(input: &str, bounds: &Bounds) -> anyhow::Result<usize> {
-    let hailstones = input.lines().map(|mut line| hailstone(&mut line).unwrap());
+    let hailstones = parse_lines(hailstone, input)?;
 
     let count = hailstones
+        .iter()
         .tuple_combinations()
         .filter(|(a, b)| a.intersects_within_bounds_in_future(b, bounds))
         .count();
@@ -118,6 +118,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day24);
     const SAMPLE1: &str = r#"19, 13, 30 @ -2, 1, -2
 18, 19, 22 @ -1, -1, -2
 20, 25, 34 @ -2, -2, -4
@@ -6,26 +6,15 @@ use common::{
         visit::IntoNodeIdentifiers,
         Graph, Undirected,
     },
-    winnow::{
-        ascii::{alpha1, space1},
-        combinator::separated,
-        prelude::*,
-        seq,
-    },
+    winnow::{ascii::alpha1, prelude::*},
     *,
 };
 use rand::{seq::SliceRandom, thread_rng};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day25, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day25>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Debug)]
@@ -35,19 +24,16 @@ struct LineOfInput<'a> {
 }
 
 fn line_of_input<'a>(input: &mut &'a str) -> PResult<LineOfInput<'a>> {
-    seq!(LineOfInput {
-        key:alpha1,
-        _:": ",
-        values: separated(1..,alpha1,space1)
-    })
-    .parse_next(input)
+    key_value(alpha1, space_list(alpha1))
+        .map(|(key, values)| LineOfInput { key, values })
+        .parse_next(input)
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
     let mut graph = Graph::<&str, (), Undirected>::new_undirected();
     let mut nodes = HashMap::new();
     // Build the graph
-    for line in input.lines().map(|mut line| line_of_input(&mut line).unwrap()) {
+    for line in parse_lines(line_of_input, input)? {
         let key_node = *nodes.entry(line.key).or_insert_with(|| graph.add_node(line.key));
         for value in line.values {
             let value_node = *nodes.entry(value).or_insert_with(|| graph.add_node(value));
@@ -70,16 +56,24 @@ fn part1(input: &str) -> anyhow::Result<usize> {
             edges_count.entry(edge).and_modify(|x| *x += 1).or_insert(1);
         }
     }
-    for (k, _) in edges_count.into_iter().sorted_by(|(_, v1), (_, v2)| v2.cmp(v1)).take(3) {
-        graph.remove_edge(k);
+    let cut = edges_count
+        .into_iter()
+        .sorted_by(|(_, v1), (_, v2)| v2.cmp(v1))
+        .take(3)
+        .map(|(k, _)| k)
+        .collect_vec();
+    if visualizing() {
+        let mut dot = DotGraph::from_petgraph(&graph, |(_, name)| name.to_string(), |_| None);
+        dot.highlight_edges(
+            cut.iter()
+                .filter_map(|&k| graph.edge_endpoints(k))
+                .map(|(a, b)| (a.index(), b.index())),
+        );
+        dot.save("day25pt1.dot")?;
     }
-
-    #[cfg(not)]
-    {
-        use common::petgraph::dot::Dot;
-        use std::io::Write;
-        let dot = Dot::with_config(&graph, &[petgraph::dot::Config::EdgeNoLabel]);
-        std::fs::File::create("day25pt1.dot")?.write_all(format!("{dot:?}").as_bytes())?;
+    // Remove the highest index first so the other indices stay valid
+    for k in cut.into_iter().sorted().rev() {
+        graph.remove_edge(k);
     }
     // Hack: I found this function when working on another problem and it just seperates the graph
     // into groups. I can reuse it here to get the 2 groups since they are separated
@@ -94,6 +88,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day25);
     const SAMPLE1: &str = r#"jqt: rhn xhk nvd
 rsh: frs pzl lsr
 xhk: hfx
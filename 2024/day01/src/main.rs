@@ -1,16 +1,10 @@
 use common::*;
 use num::abs;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day01, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day01>(&read_input_file_for_project_as_string!())
 }
 
 fn parse_in(input: &str) -> (Vec<i32>, Vec<i32>) {
@@ -56,6 +50,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day01);
     const SAMPLE1: &str = r#"3   4
 4   3
 2   5
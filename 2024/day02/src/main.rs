@@ -2,16 +2,10 @@ use std::{cmp::Ordering, usize};
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day02, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day02>(&read_input_file_for_project_as_string!())
 }
 
 enum IsLevelSafe {
@@ -92,6 +86,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day02);
     const SAMPLE1: &str = r#"7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
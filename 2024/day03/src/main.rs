@@ -3,16 +3,10 @@ use std::collections::BTreeMap;
 use common::*;
 use regex::Regex;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day03, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day03>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -81,6 +75,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day03);
     const SAMPLE1: &str = r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#;
     #[test]
     fn p1_test() {
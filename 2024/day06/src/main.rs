@@ -2,81 +2,30 @@ use std::collections::BTreeSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
-}
+solution!(Day06, usize, usize);
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+fn main() -> R<()> {
+    run::<Day06>(&read_input_file_for_project_as_string!())
 }
 
-fn walk_or_turn(
-    grid: &Grid,
-    current_position: (usize, usize),
-    current_direction: Direction,
-) -> Option<((usize, usize), Direction)> {
-    match current_direction {
-        Direction::Up => {
-            let front = (current_position.0.wrapping_sub(1), current_position.1);
-            grid.get_char(front.0, front.1).map(|char| {
-                if char == '#' {
-                    (current_position, Direction::Right)
-                } else {
-                    (front, current_direction)
-                }
-            })
-        }
-        Direction::Right => {
-            let front = (current_position.0, current_position.1 + 1);
-            grid.get_char(front.0, front.1).map(|char| {
-                if char == '#' {
-                    (current_position, Direction::Down)
-                } else {
-                    (front, current_direction)
-                }
-            })
+/// Steps forward, or turns right when something is in the way. None once the guard walks off of the grid
+fn walk_or_turn(grid: &Grid, position: Point2<usize>, direction: Dir4) -> Option<(Point2<usize>, Dir4)> {
+    let front = position.signed().step(direction);
+    grid.at(front).map(|&char| {
+        if char == '#' {
+            (position, direction.turn_right())
+        } else {
+            (front.unsigned().expect("Front is on the grid"), direction)
         }
-        Direction::Down => {
-            let front = (current_position.0 + 1, current_position.1);
-            grid.get_char(front.0, front.1).map(|char| {
-                if char == '#' {
-                    (current_position, Direction::Left)
-                } else {
-                    (front, current_direction)
-                }
-            })
-        }
-        Direction::Left => {
-            let front = (current_position.0, current_position.1.wrapping_sub(1));
-            grid.get_char(front.0, front.1).map(|char| {
-                if char == '#' {
-                    (current_position, Direction::Up)
-                } else {
-                    (front, current_direction)
-                }
-            })
-        }
-    }
+    })
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
     let grid = Grid::new(input.to_string());
-    let start_index = grid.inner.chars().position(|x| x == '^').expect("Input should have ^");
-    let mut current_position = grid.index_to_row_col(start_index);
-    let mut current_direction = Direction::Up;
-    let mut marked_tiles: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let start_index = grid.inner.iter().position(|&x| x == '^').expect("Input should have ^");
+    let mut current_position = grid.index_to_point(start_index);
+    let mut current_direction = Dir4::Up;
+    let mut marked_tiles: BTreeSet<Point2<usize>> = BTreeSet::new();
     marked_tiles.insert(current_position);
     while let Some((position, direction)) = walk_or_turn(&grid, current_position, current_direction) {
         current_position = position;
@@ -89,7 +38,7 @@ fn part1(input: &str) -> anyhow::Result<usize> {
 
 fn part2(input: &str) -> anyhow::Result<usize> {
     let grid = Grid::new(input.to_string());
-    let start_index = grid.inner.chars().position(|x| x == '^').expect("Input should have ^");
+    let start_index = grid.inner.iter().position(|&x| x == '^').expect("Input should have ^");
     let loops = (0..grid.inner.len())
         .into_par_iter()
         .fold(
@@ -98,10 +47,10 @@ fn part2(input: &str) -> anyhow::Result<usize> {
                 let obsticle_pos = grid.index_to_row_col(i);
                 if let Some('.') = grid.get_char(obsticle_pos.0, obsticle_pos.1) {
                     let mut grid = grid.clone();
-                    grid.inner.replace_range(i..=i, "#");
+                    grid.inner[i] = '#';
 
-                    let mut current_position = grid.index_to_row_col(start_index);
-                    let mut current_direction = Direction::Up;
+                    let mut current_position = grid.index_to_point(start_index);
+                    let mut current_direction = Dir4::Up;
                     let mut visited = BTreeSet::new();
                     visited.insert((current_position, current_direction));
                     while let Some((position, direction)) = walk_or_turn(&grid, current_position, current_direction) {
@@ -124,6 +73,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day06);
     const SAMPLE1: &str = r#"....#.....
 .........#
 ..........
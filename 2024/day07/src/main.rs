@@ -3,16 +3,10 @@ use std::iter::repeat_n;
 use common::*;
 use num::pow::Pow;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day07, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day07>(&read_input_file_for_project_as_string!())
 }
 #[derive(Debug, Clone)]
 struct Equation {
@@ -118,6 +112,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day07);
     const SAMPLE1: &str = r#"190: 10 19
 3267: 81 40 27
 83: 17 5
@@ -135,4 +130,49 @@ mod tests {
     fn p2_test() {
         assert_eq!(part2(SAMPLE1).unwrap(), 11387);
     }
+
+    /// Equations built by applying random operators so every line can be solved, `concat` lets `||` be used
+    struct Equations {
+        lines: usize,
+        concat: bool,
+    }
+
+    impl InputGenerator for Equations {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            (0..self.lines)
+                .map(|_| {
+                    let numbers = (0..rng.gen_range(2..6)).map(|_| rng.gen_range(1..20)).collect_vec();
+                    let test_value = numbers[1..]
+                        .iter()
+                        .fold(numbers[0], |acc, &x| match rng.gen_range(0..3) {
+                            0 => acc + x,
+                            1 => acc * x,
+                            _ if self.concat => format!("{acc}{x}").parse().unwrap(),
+                            _ => acc + x,
+                        });
+                    format!("{test_value}: {}", numbers.iter().join(" "))
+                })
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn solvable_equations() {
+        let total = |input: &str| input.lines().map(|line| parse_input(line).test_value).sum::<usize>();
+        for seed in 0..20 {
+            let input = Equations {
+                lines: 10,
+                concat: false,
+            }
+            .generate_seeded(seed);
+            assert_eq!(part1(&input).unwrap(), total(&input), "{input}");
+            assert_eq!(part2(&input).unwrap(), total(&input), "{input}");
+            let input = Equations {
+                lines: 10,
+                concat: true,
+            }
+            .generate_seeded(seed);
+            assert_eq!(part2(&input).unwrap(), total(&input), "{input}");
+        }
+    }
 }
@@ -2,16 +2,10 @@ use std::collections::{BTreeMap, HashSet};
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day08, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day08>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -40,7 +34,8 @@ fn part1(input: &str) -> anyhow::Result<usize> {
 fn parse_input(grid: &Grid) -> BTreeMap<char, Vec<(usize, usize)>> {
     let mut mapping = BTreeMap::new();
     grid.inner
-        .chars()
+        .iter()
+        .copied()
         .enumerate()
         .filter(|(_, x)| *x != '.')
         .for_each(|(i, c)| {
@@ -85,6 +80,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day08);
     const SAMPLE1: &str = r#"............
 ........0...
 .....0......
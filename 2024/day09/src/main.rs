@@ -1,21 +1,11 @@
-use std::{
-    char,
-    fmt::{format, Display},
-    ops::Index,
-};
+use std::ops::Index;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day09, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day09>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -33,7 +23,10 @@ fn part1(input: &str) -> anyhow::Result<usize> {
             (0..size).for_each(|_| memory.push(None));
         }
     }
-    //debug_print(&memory);
+    let mut animation = Animation::new();
+    if visualizing() {
+        animation.push(memory_frame(&memory).with_caption("Before"));
+    }
     let mut start = 0;
     let mut current = memory.len() - 1;
     while start < current {
@@ -45,7 +38,10 @@ fn part1(input: &str) -> anyhow::Result<usize> {
         }
         memory.swap(start, current);
     }
-    // debug_print(&memory);
+    if visualizing() {
+        animation.push(memory_frame(&memory).with_caption("Compacted"));
+        animation.show()?;
+    }
     Ok(memory
         .into_iter()
         .flatten()
@@ -53,16 +49,29 @@ fn part1(input: &str) -> anyhow::Result<usize> {
         .fold(0, |acc, (i, x)| acc + i * x))
 }
 
-#[allow(dead_code)]
-fn debug_print(memory: &[Option<usize>]) {
-    for a in memory {
-        if let Some(x) = a {
-            print!("{x}");
-        } else {
-            print!(".");
-        }
-    }
-    println!();
+/// The file id in each block of memory, None for free space
+fn blocks(memory: &[Entry]) -> Vec<Option<usize>> {
+    memory
+        .iter()
+        .flat_map(|entry| match *entry {
+            Entry::File { id, len } => vec![Some(id); len],
+            Entry::Free { len } => vec![None; len],
+        })
+        .collect()
+}
+
+/// Memory wrapped onto rows of 100, files are drawn as the last digit of their id and free space as `.`
+fn memory_frame(memory: &[Option<usize>]) -> Frame {
+    let cols = memory.len().clamp(1, 100);
+    let mut cells = memory
+        .iter()
+        .map(|block| match block {
+            Some(id) => char::from_digit((id % 10) as u32, 10).unwrap(),
+            None => '.',
+        })
+        .collect_vec();
+    cells.resize(cells.len().div_ceil(cols) * cols, ' ');
+    Frame::new(Grid::from_vec(cells, cols)).with_colors(|c| c.is_ascii_digit().then_some(Color::Green))
 }
 
 #[derive(Debug)]
@@ -79,22 +88,6 @@ impl Entry {
         }
     }
 }
-impl Display for Entry {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        #[cfg(test)]
-        {
-            let (c, len) = match self {
-                Entry::File { id, len } => (char::from_digit(*id as u32, 10).unwrap_or('?'), len),
-                Entry::Free { len } => ('.', len),
-            };
-            write!(f, "{}", std::iter::repeat(c).take(*len).collect::<String>())
-        }
-        #[cfg(not(test))]
-        {
-            write!(f, "{:?}", &self)
-        }
-    }
-}
 
 fn part2(input: &str) -> anyhow::Result<usize> {
     let mut memory = vec![];
@@ -114,54 +107,35 @@ fn part2(input: &str) -> anyhow::Result<usize> {
             memory.push(Entry::Free { len: size as usize });
         }
     }
-    #[cfg(test)]
-    {
-        for e in &memory {
-            print!("{e}");
-        }
-        println!();
+    let mut animation = Animation::new();
+    if visualizing() {
+        animation.push(memory_frame(&blocks(&memory)).with_caption("Before"));
     }
+    let mut moves = 0;
     // Take each file from end to start
     //   check each empty area from start to end
     //   If it fits swap and decrment the index
     let mut end = memory.len() - 1;
     while end > 0 {
-        if let Entry::File { id: _, len: file_len } = memory[end] {
+        if let Entry::File { id, len: file_len } = memory[end] {
+            let moves_before = moves;
             let mut start = 0;
             loop {
                 if let Entry::Free { len: free_len } = memory[start] {
                     match free_len.cmp(&file_len) {
                         std::cmp::Ordering::Equal => {
-                            #[cfg(test)]
-                            {
-                                println!("  Swapping {} and {}", memory[start], memory[end]);
-                            }
                             memory.swap(start, end);
-                            #[cfg(test)]
-                            {
-                                debug_print2(&memory);
-                            }
+                            moves += 1;
                             end -= 1;
                             break;
                         }
                         std::cmp::Ordering::Greater => {
                             let new_len = free_len - file_len;
                             memory[start].set_len(file_len);
-                            #[cfg(test)]
-                            {
-                                println!("  Swapping {} and {}", memory[start], memory[end]);
-                            }
                             memory.swap(start, end);
                             // Insert empty space after
-                            let new = Entry::Free { len: new_len };
-                            #[cfg(test)]
-                            {
-                                debug_print2(&memory);
-                                println!("  Inserting {new}");
-                            }
-                            memory.insert(start + 1, new);
-                            #[cfg(test)]
-                            debug_print2(&memory);
+                            memory.insert(start + 1, Entry::Free { len: new_len });
+                            moves += 1;
                             break;
                         }
                         std::cmp::Ordering::Less => (),
@@ -172,9 +146,17 @@ fn part2(input: &str) -> anyhow::Result<usize> {
                 }
                 start += 1;
             }
+            // Every move would be far too many frames for a real input
+            if visualizing() && moves != moves_before && moves % 100 == 0 {
+                animation.push(memory_frame(&blocks(&memory)).with_caption(format!("Moved file {}", id)));
+            }
         }
         end -= 1;
     }
+    if visualizing() {
+        animation.push(memory_frame(&blocks(&memory)).with_caption("Compacted"));
+        animation.show()?;
+    }
 
     let ans = memory
         .into_iter()
@@ -193,21 +175,10 @@ fn part2(input: &str) -> anyhow::Result<usize> {
     Ok(ans)
 }
 
-#[allow(dead_code, unused_variables)]
-fn debug_print2(memory: &[Entry]) {
-    #[cfg(test)]
-    {
-        print!("  ");
-        for e in memory {
-            print!("{e}");
-        }
-        println!();
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day09);
     const SAMPLE1: &str = r#"2333133121414131402"#;
     #[test]
     fn p1_test() {
@@ -1,18 +1,10 @@
-use std::collections::HashMap;
-
 use common::*;
 use num::{pow, Integer};
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day11, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day11>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -42,20 +34,18 @@ fn part1(input: &str) -> anyhow::Result<usize> {
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let mut memo = HashMap::new();
+    let mut memo = Memo::named("blinks");
     let len = input
         .trim()
         .split(" ")
         .map(|x| x.parse::<usize>().expect("Should be a number"))
-        .fold(0, |acc, x| acc + process_number(x, 75, &mut memo));
+        .fold(0, |acc, x| acc + memo.call((x, 75), &process_number));
     Ok(len)
 }
 
-// Takes in one number, runs the algorith a certain number of times returning the final length
-fn process_number(x: usize, steps: usize, memo: &mut HashMap<(usize, usize), usize>) -> usize {
-    if memo.contains_key(&(x, steps)) {
-        return memo[&(x, steps)];
-    }
+// Takes in one number and how many times to run the algorithm, returning the final length. `blink` is the memoised
+// version of itself
+fn process_number(blink: &mut dyn FnMut((usize, usize)) -> usize, (x, steps): (usize, usize)) -> usize {
     if steps == 0 {
         return 1;
     }
@@ -73,16 +63,13 @@ fn process_number(x: usize, steps: usize, memo: &mut HashMap<(usize, usize), usi
             }
         }
     };
-    let sum = next
-        .into_iter()
-        .fold(0, |acc, x| acc + process_number(x, steps - 1, memo));
-    memo.insert((x, steps), sum);
-    sum
+    next.into_iter().fold(0, |acc, x| acc + blink((x, steps - 1)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day11);
     const SAMPLE1: &str = r#"125 17"#;
     #[test]
     fn p1_test() {
@@ -93,4 +80,31 @@ mod tests {
         // No example for part 2
         //assert_eq!(part2(SAMPLE1).unwrap(), 0);
     }
+
+    /// A line of stones with up to 4 digits
+    struct Stones {
+        count: usize,
+    }
+
+    impl InputGenerator for Stones {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            (0..self.count).map(|_| rng.gen_range(0..10000)).join(" ")
+        }
+    }
+
+    #[test]
+    fn memo_difftest() {
+        // Part 2's memoised count after the same 25 blinks as part 1's list
+        let memo_count = |input: &String| {
+            let mut memo = Memo::new();
+            input
+                .split(' ')
+                .map(|x| memo.call((x.parse().unwrap(), 25), &process_number))
+                .sum::<usize>()
+        };
+        DiffTest::for_generator(|input| part1(input).unwrap(), memo_count, Stones { count: 4 })
+            .with_cases(20)
+            .run()
+            .unwrap();
+    }
 }
@@ -2,16 +2,10 @@ use std::{cmp::Ordering, collections::HashSet};
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day14, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day14>(&read_input_file_for_project_as_string!())
 }
 #[cfg(test)]
 const WIDTH: isize = 11;
@@ -117,6 +111,16 @@ fn part2(input: &str) -> anyhow::Result<usize> {
         robots.iter_mut().for_each(|x| x.step());
         time += 1;
     }
+    if visualizing() {
+        let mut picture = Grid::filled(HEIGHT as usize, WIDTH as usize, '.');
+        for robot in &robots {
+            picture[Point2::new(robot.pos.0 as usize, robot.pos.1 as usize)] = '#';
+        }
+        let frame = Frame::new(picture)
+            .with_caption(format!("After {} seconds", time))
+            .with_colors(|c| (c == '#').then_some(Color::Green));
+        [frame].into_iter().collect::<Animation>().show()?;
+    }
 
     Ok(time)
 }
@@ -124,6 +128,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day14);
     const SAMPLE1: &str = r#"p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
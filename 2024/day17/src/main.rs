@@ -1,17 +1,14 @@
-use common::*;
+use common::{
+    winnow::ascii::{line_ending, multispace0, multispace1},
+    *,
+};
 use num::{pow, FromPrimitive};
 use num_derive::FromPrimitive;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day17, String, usize);
+
+fn main() -> R<()> {
+    run::<Day17>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(FromPrimitive)]
@@ -37,31 +34,30 @@ struct Computer {
 }
 
 impl Computer {
-    fn new(input: &str) -> Self {
-        let mut iter = input.lines();
-        let a = iter.next().unwrap().split(":").nth(1).unwrap().trim().parse().unwrap();
-        let b = iter.next().unwrap().split(":").nth(1).unwrap().trim().parse().unwrap();
-        let c = iter.next().unwrap().split(":").nth(1).unwrap().trim().parse().unwrap();
-        let _ = iter.next();
-        let program = iter
-            .next()
-            .unwrap()
-            .split(":")
-            .nth(1)
-            .unwrap()
-            .trim()
-            .split(",")
-            .map(|x| x.parse().unwrap())
-            .collect_vec();
+    fn new(input: &str) -> R<Self> {
+        let register = |name| key_value(name, int);
+        let ((_, a), _, (_, b), _, (_, c), _, (_, program), _) = parse_all(
+            (
+                register("Register A"),
+                line_ending,
+                register("Register B"),
+                line_ending,
+                register("Register C"),
+                multispace1,
+                key_value("Program", comma_list(int)),
+                multispace0,
+            ),
+            input,
+        )?;
 
-        Self {
+        Ok(Self {
             a,
             b,
             c,
             ip: 0,
             program,
             out: vec![],
-        }
+        })
     }
     fn run(&mut self) -> anyhow::Result<()> {
         loop {
@@ -116,13 +112,13 @@ impl Computer {
 }
 
 fn part1(input: &str) -> anyhow::Result<String> {
-    let mut c = Computer::new(input);
+    let mut c = Computer::new(input)?;
     let _ = c.run();
     Ok(c.out.into_iter().join(","))
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let mut c = Computer::new(input);
+    let mut c = Computer::new(input)?;
     let output_len = c.program.len();
     let mut a = pow(8, output_len - 1);
     let mut digit = output_len - 2;
@@ -146,6 +142,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day17);
     const SAMPLE1: &str = r#"Register A: 729
 Register B: 0
 Register C: 0
@@ -1,17 +1,11 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::BTreeSet;
 
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day19, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day19>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -26,14 +20,11 @@ fn part1(input: &str) -> anyhow::Result<usize> {
     // Ignore empty line
     let _ = lines.next();
     let mut count = 0;
+    let mut memo = Memo::named("towel orders");
     for line in lines {
-        println!("{}", &line);
-        let mut memo = HashMap::new();
-        if let Some(x) = check(line, 0, &available_towels, &mut memo) {
+        memo.clear();
+        if check(line, 0, &available_towels, &mut memo).is_some() {
             count += 1;
-            println!("  Pass {x:?}");
-        } else {
-            println!("  Fail");
         }
     }
     Ok(count)
@@ -43,36 +34,24 @@ fn check(
     line: &str,
     index: usize,
     available_towels: &BTreeSet<(usize, String)>,
-    memo: &mut HashMap<(usize, usize), Option<Vec<String>>>,
+    memo: &mut Memo<usize, Option<Vec<String>>>,
 ) -> Option<Vec<String>> {
     if index == line.len() {
         return Some(vec![]);
     }
-    let possible_towels = available_towels
-        .iter()
-        .filter(|(_, t)| {
-            let len = t.len();
-            (index + len) <= line.len() && &line[index..index + len] == t
-        })
-        .collect_vec();
-    let mut res = None;
-    for (i, t) in possible_towels {
-        if res.is_some() {
-            break;
-        }
-        if memo.contains_key(&(index, *i)) {
-            res = memo.get(&(index, *i)).unwrap().to_owned();
-        } else if let Some(mut x) = check(line, index + t.len(), available_towels, memo) {
-            x.push(t.to_owned());
-            res = Some(x);
-            memo.insert((index, *i), res.clone());
-            break;
-        } else {
-            memo.insert((index, *i), None);
-        }
-    }
-
-    res
+    memo.get_or_insert_with(index, |memo| {
+        available_towels
+            .iter()
+            .filter(|(_, t)| {
+                let len = t.len();
+                (index + len) <= line.len() && &line[index..index + len] == t
+            })
+            .find_map(|(_, t)| {
+                let mut x = check(line, index + t.len(), available_towels, memo)?;
+                x.push(t.to_owned());
+                Some(x)
+            })
+    })
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
@@ -87,9 +66,9 @@ fn part2(input: &str) -> anyhow::Result<usize> {
     // Ignore empty line
     let _ = lines.next();
     let mut count = 0;
+    let mut memo = Memo::named("towel arrangements");
     for line in lines {
-        println!("{}", &line);
-        let mut memo = HashMap::new();
+        memo.clear();
         count += check2(line, 0, &available_towels, &mut memo);
     }
     Ok(count)
@@ -99,35 +78,27 @@ fn check2(
     line: &str,
     index: usize,
     available_towels: &BTreeSet<(usize, String)>,
-    memo: &mut HashMap<(usize, usize), usize>,
+    memo: &mut Memo<usize, usize>,
 ) -> usize {
     if index == line.len() {
         return 1;
     }
-    let possible_towels = available_towels
-        .iter()
-        .filter(|(_, t)| {
-            let len = t.len();
-            (index + len) <= line.len() && &line[index..index + len] == t
-        })
-        .collect_vec();
-    let mut res = 0;
-    for (i, t) in possible_towels {
-        if memo.contains_key(&(index, *i)) {
-            res += memo.get(&(index, *i)).unwrap().to_owned();
-        } else {
-            let x = check2(line, index + t.len(), available_towels, memo);
-            res += x;
-            memo.insert((index, *i), x);
-        }
-    }
-
-    res
+    memo.get_or_insert_with(index, |memo| {
+        available_towels
+            .iter()
+            .filter(|(_, t)| {
+                let len = t.len();
+                (index + len) <= line.len() && &line[index..index + len] == t
+            })
+            .map(|(_, t)| check2(line, index + t.len(), available_towels, memo))
+            .sum()
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day19);
     const SAMPLE1: &str = r#"r, wr, b, g, bwu, rb, gb, br
 
 brwrr
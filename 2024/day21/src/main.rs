@@ -1,22 +1,16 @@
 use std::{
-    cmp::{min_by, Ordering},
+    cmp::min_by,
     collections::{BTreeMap, HashMap},
-    usize, vec,
+    usize,
 };
 
 use common::*;
 use winnow::error::InputError;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day21, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day21>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Clone, Debug)]
@@ -25,80 +19,49 @@ struct KeyPad {
     char_positions: BTreeMap<char, (usize, usize)>,
 }
 
+/// Every shortest way to press each key after each other key
+type Paths = HashMap<(char, char), Vec<Vec<char>>>;
+
 impl KeyPad {
-    fn find_all_shortest_paths(&self) -> HashMap<(char, char), Vec<Vec<char>>> {
-        let mut map = HashMap::new();
-        for perm in self.grid.inner.chars().filter(|c| *c != ' ').combinations(2) {
-            let (a, b) = (perm[0], perm[1]);
-            let mut paths = self.travel(self.grid.find_char(a).unwrap(), self.grid.find_char(b).unwrap(), vec![]);
-            map.insert((a, b), paths.clone());
-            {
-                paths.iter_mut().for_each(|x| {
-                    let a = x.pop().unwrap();
-                    x.reverse();
-                    x.iter_mut().for_each(|x| {
-                        *x = match x {
-                            'v' => '^',
-                            '^' => 'v',
-                            '<' => '>',
-                            '>' => '<',
-                            'A' => 'A',
-                            _ => unreachable!("Invalid character"),
-                        }
-                    });
-                    x.push(a);
-                });
-                map.insert((b, a), paths);
-            }
-        }
-        map
+    fn find_all_shortest_paths(&self) -> Paths {
+        let keys = self.grid.inner.iter().copied().filter(|c| *c != ' ');
+        keys.clone()
+            .cartesian_product(keys)
+            .map(|(a, b)| ((a, b), self.shortest_presses(a, b)))
+            .collect()
     }
-    fn check_new_point(&self, row: usize, col: usize) -> Option<(usize, usize)> {
-        if let Some(' ') | None = self.grid.get_char(row, col) {
-            None
-        } else {
-            Some((row, col))
-        }
+
+    /// Every shortest way to move from key `a` to `b` as the arrows to press followed by A
+    fn shortest_presses(&self, a: char, b: char) -> Vec<Vec<char>> {
+        let position = |key| self.grid.position_point(|&c| c == key).unwrap().signed();
+        let end = position(b);
+        let successors = |&p: &Point2<isize>| {
+            p.neighbors4()
+                .filter(|&next| self.grid.at(next).is_some_and(|&c| c != ' '))
+                .map(|next| (next, 1))
+                .collect_vec()
+        };
+        let (_, paths) = all_shortest_paths([position(a)], successors, |&p| p == end).unwrap();
+        paths
+            .into_iter()
+            .map(|path| {
+                path.windows(2)
+                    .map(|step| arrow(step[1] - step[0]))
+                    .chain(['A'])
+                    .collect()
+            })
+            .collect()
     }
-    fn travel(&self, current: (usize, usize), dest: (usize, usize), mut path: Vec<char>) -> Vec<Vec<char>> {
-        let neighbors = [
-            match current.0.cmp(&dest.0) {
-                // Need to add
-                Ordering::Less => self.check_new_point(current.0 + 1, current.1).map(|x| ('v', x)),
-                // Need to do nothing
-                Ordering::Equal => None,
-                // Need to sub
-                Ordering::Greater => self
-                    .check_new_point(current.0.wrapping_sub(1), current.1)
-                    .map(|x| ('^', x)),
-            },
-            match current.1.cmp(&dest.1) {
-                Ordering::Less => self.check_new_point(current.0, current.1 + 1).map(|x| ('>', x)),
-                Ordering::Equal => None,
-                Ordering::Greater => self
-                    .check_new_point(current.0, current.1.wrapping_sub(1))
-                    .map(|x| ('<', x)),
-            },
-        ];
-        match neighbors {
-            [None, None] => {
-                assert_eq!(current, dest, "If there are no valid neighbors, must be at destination");
-                path.push('A');
-                vec![path]
-            }
-            [None, Some(x)] | [Some(x), None] => {
-                path.push(x.0);
-                self.travel(x.1, dest, path)
-            }
-            [Some(x), Some(y)] => {
-                let mut path2 = path.clone();
-                path2.push(x.0);
-                let mut x = self.travel(x.1, dest, path2);
-                path.push(y.0);
-                x.append(&mut self.travel(y.1, dest, path));
-                x
-            }
-        }
+}
+
+/// Key for moving by one step
+fn arrow(delta: Point2<isize>) -> char {
+    match Dir4::ALL.into_iter().find(|dir| dir.delta() == delta) {
+        Some(Dir4::Up) => '^',
+        Some(Dir4::Right) => '>',
+        Some(Dir4::Down) => 'v',
+        Some(Dir4::Left) => '<',
+        None => unreachable!("Only single steps are made"),
     }
 }
 
@@ -129,61 +92,66 @@ fn get_initial() -> (KeyPad, KeyPad, KeyPad) {
 
 fn part1(input: &str) -> anyhow::Result<usize> {
     let mut res = 0;
-    let (mut numpad, mut keypad1, mut keypad2) = get_initial();
+    let (numpad, keypad, _) = get_initial();
     let numpad_paths = numpad.find_all_shortest_paths();
-    let keypad_paths = keypad1.find_all_shortest_paths();
-    println!("\n{keypad_paths:?}");
+    let keypad_paths = keypad.find_all_shortest_paths();
+    let mut memo = Memo::named("key presses");
     for line in input.lines() {
-        let mut out = vec![];
-        let input = line.chars().collect::<Vec<_>>();
-        let expanded = expand(input, &numpad_paths);
-        for input in expanded {
-            let expanded2 = expand(input, &keypad_paths);
-            for input in expanded2 {
-                let expanded3 = expand(input, &keypad_paths);
-                out.push(expanded3);
-            }
+        let mut position = 'A';
+        let mut presses = 0;
+        for key in line.chars() {
+            // Two robots on directional keypads sit between the numpad's robot and the person
+            presses += numpad_paths[&(position, key)]
+                .iter()
+                .map(|path| sequence_cost(path, 2, &keypad_paths, &mut memo))
+                .min()
+                .unwrap();
+            position = key;
         }
-        let mut out = out.into_iter().flatten().collect_vec();
-        out.sort_by_key(|x1| x1.len());
-        let min_len = out[0].len();
-        let number: usize = line[..line.len() - 1].parse().unwrap();
-        res += dbg!(min_len * number);
+        let number: usize = line[..line.len() - 1].parse()?;
+        res += presses * number;
     }
     Ok(res)
 }
 
-fn expand(input: Vec<char>, a: &HashMap<(char, char), Vec<Vec<char>>>) -> Vec<Vec<char>> {
-    let mut paths = vec![];
+/// Presses the person makes so the keys are typed on a directional keypad with `robots` robots between it and the
+/// person, the robot typing them starts on A
+fn sequence_cost(
+    keys: &[char],
+    robots: usize,
+    keypad_paths: &Paths,
+    memo: &mut Memo<(char, char, usize), usize>,
+) -> usize {
     let mut position = 'A';
-    for new in input {
-        let new_paths = if position == new {
-            // Same character, just press A
-            vec![vec!['A']]
-        } else {
-            a[&(position, new)].clone()
-        };
-        if paths.is_empty() {
-            // Initialize the paths
-            paths = new_paths;
-        } else {
-            // For each existing path, append each new path (and clone if there are more than 1)
-            paths = paths
-                .into_iter()
-                .flat_map(|p| {
-                    let mut new = vec![];
-                    for mut np in new_paths.clone() {
-                        let mut a = p.clone();
-                        a.append(&mut np);
-                        new.push(a);
-                    }
-                    new
-                })
-                .collect::<Vec<_>>();
-        }
-        position = new;
+    keys.iter()
+        .map(|&key| {
+            let cost = press_cost(position, key, robots, keypad_paths, memo);
+            position = key;
+            cost
+        })
+        .sum()
+}
+
+/// Presses the person makes to move from key `from` to `to` and press it, on a directional keypad with `robots`
+/// robots between it and the person
+fn press_cost(
+    from: char,
+    to: char,
+    robots: usize,
+    keypad_paths: &Paths,
+    memo: &mut Memo<(char, char, usize), usize>,
+) -> usize {
+    if robots == 0 {
+        // The person presses the key themselves
+        return 1;
     }
-    paths
+    memo.get_or_insert_with((from, to, robots), |memo| {
+        keypad_paths[&(from, to)]
+            .iter()
+            .map(|path| sequence_cost(path, robots - 1, keypad_paths, memo))
+            .min()
+            .unwrap()
+    })
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
@@ -193,6 +161,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day21);
     const SAMPLE1: &str = r#"029A
 980A
 179A
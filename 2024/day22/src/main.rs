@@ -3,16 +3,10 @@ use std::collections::{vec_deque, HashMap, VecDeque};
 use common::*;
 use num::pow;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day22, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day22>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -92,6 +86,7 @@ fn most_significant_digit(x: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day22);
     const SAMPLE1: &str = r#"1
 10
 100
@@ -3,19 +3,12 @@ use std::{collections::VecDeque, path::Component};
 use common::*;
 use petgraph::{
     adj::NodeIndex,
-    dot::{Config, Dot},
     visit::{depth_first_search, Control, DfsEvent, IntoNeighbors, IntoNodeIdentifiers, Time},
 };
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+solution!(Day23, usize, usize);
+
+fn main() -> R<()> {
+    run::<Day23>(&read_input_file_for_project_as_string!())
 }
 
 fn part1(input: &str) -> anyhow::Result<usize> {
@@ -25,7 +18,9 @@ fn part1(input: &str) -> anyhow::Result<usize> {
         graph.add_edge(a.0, a.1, 1);
     }
     let graph = graph.into_graph::<NodeIndex>();
-    println!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
+    if visualizing() {
+        DotGraph::from_petgraph(&graph, |(_, name)| name.to_string(), |_| None).save("day23.dot")?;
+    }
     let mut paths = vec![];
     for node in graph.node_identifiers() {
         let mut scratch = vec![];
@@ -71,6 +66,7 @@ fn part2(input: &str) -> anyhow::Result<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    answer_tests!(Day23);
     const SAMPLE1: &str = r#"kh-tc
 qp-kh
 de-cg
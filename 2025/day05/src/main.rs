@@ -1,22 +1,14 @@
-use std::{cmp::Ordering, collections::HashSet, ops::RangeInclusive};
+use common::*;
 
-use common::{anyhow::bail, winnow::Parser, *};
+solution!(Day05, usize, usize);
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
-    }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
-    }
+fn main() -> R<()> {
+    run::<Day05>(&read_input_file_for_project_as_string!())
 }
 
 #[derive(Default)]
 struct Database {
-    ranges: Vec<RangeInclusive<usize>>,
+    ranges: IntervalSet<usize>,
     ingredients: Vec<usize>,
 }
 
@@ -31,7 +23,7 @@ fn parse_input(input: &str) -> Database {
         if parse_ranges {
             let (min, max) = line.split_once('-').expect("Range has a -");
             let (min, max) = (min.parse::<usize>().unwrap(), max.parse().unwrap());
-            db.ranges.push(min..=max);
+            db.ranges.insert(min..=max);
         } else {
             db.ingredients.push(line.parse().unwrap());
         }
@@ -41,42 +33,20 @@ fn parse_input(input: &str) -> Database {
 
 fn part1(input: &str) -> anyhow::Result<usize> {
     let Database { ranges, ingredients } = parse_input(input);
-    let mut sum = 0;
-    for id in ingredients {
-        for range in &ranges {
-            if range.contains(&id) {
-                sum += 1;
-                break;
-            }
-        }
-    }
-    Ok(sum)
+    Ok(ingredients.into_iter().filter(|&id| ranges.contains(id)).count())
 }
 
 fn part2(input: &str) -> anyhow::Result<usize> {
-    let Database {
-        mut ranges,
-        ingredients: _,
-    } = parse_input(input);
-    let mut sum = 0;
-    ranges.sort_by(|a, b| (a.start(), a.end()).cmp(&(b.start(), b.end())));
-    dbg!(&ranges);
-    let mut end = 0;
-    for mut range in ranges {
-        if *range.start() <= end {
-            range = (end + 1)..=*range.end();
-        }
-        if !range.is_empty() {
-            sum += range.end() - range.start() + 1;
-        }
-        end = *range.end();
-    }
-    Ok(sum)
+    let Database { ranges, .. } = parse_input(input);
+    Ok(ranges.len())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use super::*;
+    answer_tests!(Day05);
     const SAMPLE1: &str = r#"3-5
 10-14
 16-20
@@ -96,4 +66,50 @@ mod tests {
     fn p2_test() {
         assert_eq!(part2(SAMPLE1).unwrap(), 14);
     }
+
+    /// Overlapping fresh ranges then the ingredient ids to check, some outside every range
+    struct Inventory {
+        ranges: usize,
+        ingredients: usize,
+    }
+
+    impl InputGenerator for Inventory {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            let ranges = (0..self.ranges).map(|_| {
+                let min = rng.gen_range(0..100);
+                format!("{}-{}", min, min + rng.gen_range(0..20))
+            });
+            let ranges = ranges.collect_vec().join("\n");
+            let ingredients = (0..self.ingredients).map(|_| rng.gen_range(0..130)).join("\n");
+            format!("{ranges}\n\n{ingredients}")
+        }
+    }
+
+    #[test]
+    fn inventory_difftest() {
+        // Every id in a range, one at a time
+        let brute_force = |input: &String| {
+            let (ranges, ingredients) = input.split_once("\n\n").unwrap();
+            let fresh = ranges
+                .lines()
+                .flat_map(|line| {
+                    let (min, max) = line.split_once('-').unwrap();
+                    min.parse::<usize>().unwrap()..=max.parse().unwrap()
+                })
+                .collect::<HashSet<_>>();
+            let ingredients = ingredients.lines().map(|id| id.parse::<usize>().unwrap());
+            (ingredients.filter(|id| fresh.contains(id)).count(), fresh.len())
+        };
+        let inventory = Inventory {
+            ranges: 6,
+            ingredients: 10,
+        };
+        DiffTest::for_generator(
+            brute_force,
+            |input| (part1(input).unwrap(), part2(input).unwrap()),
+            inventory,
+        )
+        .run()
+        .unwrap();
+    }
 }
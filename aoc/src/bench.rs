@@ -0,0 +1,218 @@
+// Compares benchmark results to earlier runs saved in a JSON history file
+
+use std::{
+    fs,
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use common::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{runner::RunResult, table::Table};
+
+/// Every benchmark run, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<HistoryRun>,
+}
+
+/// Results of one `aoc bench`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryRun {
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    /// Commit that was checked out, if it could be found
+    pub commit: Option<String>,
+    pub results: Vec<BenchRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub year: u16,
+    pub day: u8,
+    pub profile: String,
+    pub part: u8,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub runs: usize,
+}
+
+impl History {
+    /// Loads the history, a missing file has no runs
+    pub fn load(path: &Path) -> R<Self> {
+        if path.is_file() {
+            Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> R<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Latest saved result for the same part of the same day and profile
+    pub fn previous(&self, record: &BenchRecord) -> Option<&BenchRecord> {
+        self.runs.iter().rev().find_map(|run| {
+            run.results.iter().find(|r| {
+                (r.year, r.day, r.part, &r.profile) == (record.year, record.day, record.part, &record.profile)
+            })
+        })
+    }
+}
+
+/// How much slower the median got in percent, negative when it got faster
+pub fn change(previous: &BenchRecord, current: &BenchRecord) -> f64 {
+    (current.median_ns as f64 - previous.median_ns as f64) / previous.median_ns.max(1) as f64 * 100.0
+}
+
+/// Prints the results next to the previous ones and adds them to the history.
+///
+/// Errors when a part got more than `threshold` percent slower or a day failed to run. Days that ran but printed no
+/// bench stats don't use `common::run`, they are skipped with a note
+pub fn report(results: &[RunResult], history_path: &Path, threshold: f64, save: bool) -> R<()> {
+    let default_profile = InputStore::from_env().profile().to_string();
+    let mut history = History::load(history_path)?;
+    let mut table = Table::new([
+        "Year", "Day", "Profile", "Part", "Median", "P95", "Previous", "Change", "Error",
+    ]);
+    let mut records = vec![];
+    let mut regressions = 0;
+    let mut errors = 0;
+    let mut skipped = 0;
+    for result in results {
+        let puzzle = &result.puzzle;
+        let profile = result.profile.clone().unwrap_or_else(|| default_profile.clone());
+        let benched = result
+            .parts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| Some((i as u8 + 1, p.bench?)));
+        let mut any = false;
+        for (part, stats) in benched {
+            any = true;
+            let record = BenchRecord {
+                year: puzzle.year,
+                day: puzzle.day,
+                profile: profile.clone(),
+                part,
+                median_ns: stats.median.as_nanos() as u64,
+                p95_ns: stats.p95.as_nanos() as u64,
+                runs: stats.runs,
+            };
+            let (previous, change) = match history.previous(&record) {
+                Some(previous) => {
+                    let change = change(previous, &record);
+                    let mut cell = format!("{:+.1}%", change);
+                    if change > threshold {
+                        regressions += 1;
+                        cell += " REGRESSION";
+                    }
+                    (format!("{:.2?}", Duration::from_nanos(previous.median_ns)), cell)
+                }
+                None => (String::new(), String::new()),
+            };
+            table.push(vec![
+                puzzle.year.to_string(),
+                puzzle.day.to_string(),
+                profile.clone(),
+                part.to_string(),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+                previous,
+                change,
+                result.error.clone().unwrap_or_default(),
+            ]);
+            records.push(record);
+        }
+        if !any {
+            let error = match &result.error {
+                Some(error) => {
+                    errors += 1;
+                    error.clone()
+                }
+                None => {
+                    skipped += 1;
+                    "Skipped, the day does not use common::run".to_string()
+                }
+            };
+            let mut row = vec![puzzle.year.to_string(), puzzle.day.to_string(), profile];
+            row.extend(std::iter::repeat_n(String::new(), 5));
+            row.push(error);
+            table.push(row);
+        }
+    }
+    print!("{}", table);
+    if skipped > 0 {
+        println!("{} days were skipped since they can't be benchmarked", skipped);
+    }
+    if save && !records.is_empty() {
+        history.runs.push(HistoryRun {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            commit: current_commit(),
+            results: records,
+        });
+        history.save(history_path)?;
+    }
+    if regressions > 0 || errors > 0 {
+        Err(AdventOfCodeError::new(format!(
+            "{} parts got more than {}% slower and {} days failed to run",
+            regressions, threshold, errors
+        )))?
+    }
+    Ok(())
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, median_ns: u64) -> BenchRecord {
+        BenchRecord {
+            year: 2023,
+            day,
+            profile: "default".to_string(),
+            part: 1,
+            median_ns,
+            p95_ns: median_ns,
+            runs: 10,
+        }
+    }
+
+    #[test]
+    fn previous_results() {
+        let history = History {
+            runs: vec![
+                HistoryRun {
+                    timestamp: 1,
+                    commit: None,
+                    results: vec![record(17, 100), record(23, 500)],
+                },
+                HistoryRun {
+                    timestamp: 2,
+                    commit: None,
+                    results: vec![record(17, 200)],
+                },
+            ],
+        };
+        assert_eq!(history.previous(&record(17, 0)), Some(&record(17, 200)));
+        assert_eq!(history.previous(&record(23, 0)), Some(&record(23, 500)));
+        assert_eq!(history.previous(&record(1, 0)), None);
+        assert_eq!(change(&record(17, 200), &record(17, 250)), 25.0);
+        assert_eq!(change(&record(17, 200), &record(17, 100)), -50.0);
+    }
+}
@@ -0,0 +1,250 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use common::*;
+
+mod bench;
+mod registry;
+mod runner;
+mod submit;
+mod table;
+mod verify;
+
+use registry::{Registry, Selection};
+use runner::{PartResult, RunResult, Runner};
+use table::Table;
+
+/// Runs advent of code solutions from every year
+#[derive(Parser)]
+struct Cli {
+    /// Root of the repository, defaults to the folder this crate is in
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solutions and print their answers and timings
+    Run(RunArgs),
+    /// Run solutions and compare their answers to the ones saved in each year's answers.toml
+    Verify(VerifyArgs),
+    /// Submit an answer, the day is run to get the answer when one is not given
+    Submit(SubmitArgs),
+    /// Save the examples from each day's puzzle.html to samples/<n>.txt
+    Samples(SamplesArgs),
+    /// Time each part many times and compare to the previous results in the bench history
+    Bench(BenchArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Year to run, every day in the year is run unless a day is given
+    #[arg(required_unless_present = "all")]
+    year: Option<u16>,
+    /// Day to run
+    day: Option<u8>,
+    /// Only run a single part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Run every day of every year
+    #[arg(long, conflicts_with_all = ["year", "day"])]
+    all: bool,
+    /// Build without optimizations
+    #[arg(long)]
+    debug: bool,
+    /// Input store profile to run against, can be given more than once
+    #[arg(long)]
+    profile: Vec<String>,
+    /// Run against every profile in the input store
+    #[arg(long, conflicts_with = "profile")]
+    all_profiles: bool,
+    /// Show the frames of days that draw them, `play`, `step` or a file to write them to
+    #[arg(long, value_name = "MODE")]
+    visualize: Option<String>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    #[command(flatten)]
+    run: RunArgs,
+    /// Save answers that are not in answers.toml yet, saved answers are never changed
+    #[arg(long)]
+    record: bool,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    year: u16,
+    day: u8,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    answer: Option<String>,
+    /// Input store profile to submit for
+    #[arg(long)]
+    profile: Option<String>,
+    /// Build without optimizations
+    #[arg(long)]
+    debug: bool,
+}
+
+#[derive(Args)]
+struct SamplesArgs {
+    year: u16,
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    run: RunArgs,
+    /// Number of timed runs of each part, after a few warm up runs
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+    /// Percent a median can get slower before it is reported as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// History file, defaults to bench-history.json in the root of the repository
+    #[arg(long)]
+    history: Option<PathBuf>,
+    /// Compare to the history without adding these results to it
+    #[arg(long)]
+    no_save: bool,
+}
+
+impl RunArgs {
+    fn selection(&self) -> Selection {
+        match (self.year, self.day) {
+            (Some(year), Some(day)) => Selection::Day(year, day),
+            (Some(year), None) => Selection::Year(year),
+            _ => Selection::All,
+        }
+    }
+
+    /// Profiles to run against, None uses the profile from the environment
+    fn profiles(&self) -> R<Vec<Option<String>>> {
+        if self.all_profiles {
+            Ok(InputStore::from_env().profiles()?.into_iter().map(Some).collect())
+        } else if self.profile.is_empty() {
+            Ok(vec![None])
+        } else {
+            Ok(self.profile.iter().cloned().map(Some).collect())
+        }
+    }
+}
+
+fn main() -> R<()> {
+    let cli = Cli::parse();
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let registry = Registry::discover(&root)?;
+    match cli.command {
+        Command::Run(args) => {
+            let results = run(&registry, &args, None)?;
+            print_results(&results);
+            if results.iter().any(|r| r.error.is_some()) {
+                Err(AdventOfCodeError::new("Some solutions failed to run"))?
+            }
+            Ok(())
+        }
+        Command::Verify(args) => verify::verify(&run(&registry, &args.run, None)?, args.record),
+        Command::Submit(args) => submit::submit(
+            &registry,
+            args.year,
+            args.day,
+            args.part,
+            args.answer,
+            args.profile,
+            !args.debug,
+        ),
+        Command::Samples(args) => samples(&registry, &args),
+        Command::Bench(args) => {
+            let history = args.history.clone().unwrap_or_else(|| root.join("bench-history.json"));
+            let results = run(&registry, &args.run, Some(args.runs))?;
+            bench::report(&results, &history, args.threshold, !args.no_save)
+        }
+    }
+}
+
+fn samples(registry: &Registry, args: &SamplesArgs) -> R<()> {
+    let selection = match args.day {
+        Some(day) => Selection::Day(args.year, day),
+        None => Selection::Year(args.year),
+    };
+    for puzzle in registry.select(selection)? {
+        // Only days that have a saved page are extracted when doing a whole year
+        if args.day.is_none() && !puzzle.dir.join("puzzle.html").is_file() {
+            continue;
+        }
+        let samples = save_samples(&puzzle.dir)?;
+        println!(
+            "{} day {:02}: {} examples, answers {:?}",
+            puzzle.year,
+            puzzle.day,
+            samples.examples.len(),
+            samples.answers
+        );
+    }
+    Ok(())
+}
+
+/// Builds and runs the selected days, `bench` is the number of timed runs when benchmarking
+fn run(registry: &Registry, args: &RunArgs, bench: Option<usize>) -> R<Vec<RunResult>> {
+    let runner = Runner {
+        release: !args.debug,
+        part: args.part,
+        bench,
+        visualize: args.visualize.clone(),
+    };
+    let profiles = args.profiles()?;
+    let mut results = vec![];
+    // Build each year once so the timings are not mixed up with compile times
+    for (_, puzzles) in &registry.select(args.selection())?.into_iter().group_by(|p| p.year) {
+        let puzzles = puzzles.collect_vec();
+        runner.build(&puzzles)?;
+        for puzzle in puzzles {
+            for profile in &profiles {
+                results.push(runner.run(puzzle, profile.as_deref())?);
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn print_results(results: &[RunResult]) {
+    let mut table = Table::new(["Year", "Day", "Profile", "Part 1", "Time", "Part 2", "Time", "Error"]);
+    let mut multi_line = vec![];
+    for result in results {
+        let mut row = vec![
+            result.puzzle.year.to_string(),
+            result.puzzle.day.to_string(),
+            result.profile.clone().unwrap_or_default(),
+        ];
+        for (index, part) in result.parts.iter().enumerate() {
+            row.push(answer_cell(part));
+            row.push(part.time.map(|t| format!("{:.2?}", t)).unwrap_or_default());
+            if let Some(answer) = part.answer.as_ref().filter(|a| a.contains('\n')) {
+                multi_line.push((result, index + 1, answer));
+            }
+        }
+        row.push(result.error.clone().unwrap_or_default());
+        table.push(row);
+    }
+    print!("{}", table);
+    for (result, part, answer) in multi_line {
+        println!(
+            "\n{} day {:02} part {}:\n{}",
+            result.puzzle.year, result.puzzle.day, part, answer
+        );
+    }
+}
+
+fn answer_cell(part: &PartResult) -> String {
+    match &part.answer {
+        Some(answer) if answer.contains('\n') => format!("({} lines)", answer.lines().count()),
+        Some(answer) => answer.clone(),
+        None => "-".to_string(),
+    }
+}
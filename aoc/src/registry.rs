@@ -0,0 +1,132 @@
+// Finds every year workspace and day crate in the repository
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::*;
+
+/// A single day's solution crate
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    /// Folder of the day's crate Ex: 2023/day17
+    pub dir: PathBuf,
+}
+
+impl Puzzle {
+    /// Name of the package and the binary it builds Ex: day17
+    pub fn package(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    /// Folder of the cargo workspace the day is a member of
+    pub fn workspace(&self) -> &Path {
+        self.dir.parent().expect("Day folder should be inside of a year folder")
+    }
+}
+
+/// Which puzzles to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+}
+
+/// Every puzzle found in the repository sorted by year then day
+#[derive(Debug)]
+pub struct Registry {
+    puzzles: Vec<Puzzle>,
+}
+
+impl Registry {
+    /// Scans the repository root for year folders (4 digits with a Cargo.toml) and the day folders in them
+    pub fn discover<P>(root: P) -> R<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let mut puzzles = vec![];
+        for year_dir in fs::read_dir(root)? {
+            let year_dir = year_dir?.path();
+            let Some(year) = parse_year(&year_dir) else {
+                continue;
+            };
+            if !year_dir.join("Cargo.toml").is_file() {
+                continue;
+            }
+            for day_dir in fs::read_dir(&year_dir)? {
+                let day_dir = day_dir?.path();
+                let Some(day) = parse_day(&day_dir) else {
+                    continue;
+                };
+                if day_dir.join("Cargo.toml").is_file() {
+                    puzzles.push(Puzzle {
+                        year,
+                        day,
+                        dir: day_dir,
+                    });
+                }
+            }
+        }
+        puzzles.sort();
+        Ok(Self { puzzles })
+    }
+
+    /// Returns the puzzles in the selection, errors if nothing matches
+    pub fn select(&self, selection: Selection) -> R<Vec<&Puzzle>> {
+        let selected = self
+            .puzzles
+            .iter()
+            .filter(|p| match selection {
+                Selection::All => true,
+                Selection::Year(year) => p.year == year,
+                Selection::Day(year, day) => p.year == year && p.day == day,
+            })
+            .collect_vec();
+        if selected.is_empty() {
+            Err(AdventOfCodeError::new(format!(
+                "No solutions found for {:?}",
+                selection
+            )))?
+        }
+        Ok(selected)
+    }
+}
+
+/// Year folders are named after the year Ex: 2023
+fn parse_year(path: &Path) -> Option<u16> {
+    let name = path.file_name()?.to_str()?;
+    if name.len() == 4 && name.chars().all(|c| c.is_ascii_digit()) {
+        name.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Day folders end with 2 digits for the date Ex: day01
+fn parse_day(path: &Path) -> Option<u8> {
+    let name = path.file_name()?.to_str()?;
+    let digits = name.strip_prefix("day")?;
+    if digits.len() == 2 {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folder_names() {
+        assert_eq!(parse_year(Path::new("../2023")), Some(2023));
+        assert_eq!(parse_year(Path::new("../common")), None);
+        assert_eq!(parse_day(Path::new("2023/day07")), Some(7));
+        assert_eq!(parse_day(Path::new("2023/day7")), None);
+        assert_eq!(parse_day(Path::new("2023/src")), None);
+    }
+}
@@ -0,0 +1,268 @@
+// Builds and runs the day binaries, collecting the answers and timings they print.
+//
+// Days are separate crates in separate workspaces so they can't be linked in and called, instead their stdout is the
+// interface. The lines read are:
+// - `Part1: <answer>` or `Part 1(<note>): <answer>`, only the first answer of each part is kept
+// - `Part2:` on its own followed by the lines of a multi-line answer (Ex: ascii art), which end at the next line below
+// - `Time of Part 2, <Duration as Debug>` printed by `Timer`
+// - `Bench of Part 2, median <Duration>, p95 <Duration>, runs <n>` printed by `common::run` under `AOC_BENCH`
+//
+// Every other line is ignored, so debug output is safe anywhere except inside a multi-line answer. `common::run`
+// prints the answer and its `Timer` line back to back so nothing can get in between.
+
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use common::{regex::Regex, *};
+
+use crate::registry::Puzzle;
+
+/// Answer to a single part along with the time reported by its `Timer`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartResult {
+    pub answer: Option<String>,
+    pub time: Option<Duration>,
+    /// Set when the day was run with `AOC_BENCH`
+    pub bench: Option<BenchStats>,
+}
+
+/// Everything collected from running one day
+#[derive(Debug)]
+pub struct RunResult {
+    pub puzzle: Puzzle,
+    /// Input store profile the day was run against
+    pub profile: Option<String>,
+    pub parts: [PartResult; 2],
+    /// Set when the day failed to run, holds the panic or error message
+    pub error: Option<String>,
+}
+
+pub struct Runner {
+    /// Build with `--release`
+    pub release: bool,
+    /// Only report this part (1 or 2), both parts are reported when None
+    pub part: Option<u8>,
+    /// Number of timed runs of each part, days not using `common::run` can't be benchmarked
+    pub bench: Option<usize>,
+    /// How days draw their frames, passed on as `AOC_VISUALIZE`. Stdin and stderr are left attached to the terminal so
+    /// the frames can be seen and stepped through
+    pub visualize: Option<String>,
+}
+
+impl Runner {
+    /// Builds the given days, all days must be in the same year workspace
+    pub fn build(&self, puzzles: &[&Puzzle]) -> R<()> {
+        let Some(first) = puzzles.first() else {
+            return Ok(());
+        };
+        let mut command = Command::new("cargo");
+        command.current_dir(first.workspace()).args(["build", "-q"]);
+        if self.release {
+            command.arg("--release");
+        }
+        for puzzle in puzzles {
+            command.args(["-p", &puzzle.package()]);
+        }
+        let status = command.status()?;
+        if !status.success() {
+            Err(AdventOfCodeError::new(format!("Failed to build {}", first.year)))?
+        }
+        Ok(())
+    }
+
+    /// Runs an already built day and parses what it printed, the profile picks which input from the store is used
+    pub fn run(&self, puzzle: &Puzzle, profile: Option<&str>) -> R<RunResult> {
+        let mut command = Command::new(self.binary(puzzle));
+        command.current_dir(&puzzle.dir).stdin(Stdio::null());
+        if let Some(profile) = profile {
+            command.env("AOC_PROFILE", profile);
+        }
+        if let Some(part) = self.part {
+            // Days using `common::run` only run this part, others run both and get filtered below
+            command.env("AOC_PART", part.to_string());
+        }
+        if let Some(runs) = self.bench {
+            command.env("AOC_BENCH", runs.to_string());
+        }
+        if let Some(visualize) = &self.visualize {
+            command
+                .env("AOC_VISUALIZE", visualize)
+                .stdin(Stdio::inherit())
+                .stderr(Stdio::inherit());
+        }
+        let output = command.output()?;
+        let mut parts = parse_output(&String::from_utf8_lossy(&output.stdout));
+        if let Some(part) = self.part {
+            for (index, result) in parts.iter_mut().enumerate() {
+                if index + 1 != part as usize {
+                    *result = PartResult::default();
+                }
+            }
+        }
+        let error = if output.status.success() {
+            None
+        } else {
+            Some(parse_error(&String::from_utf8_lossy(&output.stderr)))
+        };
+        Ok(RunResult {
+            puzzle: puzzle.clone(),
+            profile: profile.map(str::to_string),
+            parts,
+            error,
+        })
+    }
+
+    fn binary(&self, puzzle: &Puzzle) -> PathBuf {
+        let profile = if self.release { "release" } else { "debug" };
+        // Cargo resolves a relative CARGO_TARGET_DIR from the workspace it was run in
+        let target = std::env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+        puzzle.workspace().join(target).join(profile).join(format!(
+            "{}{}",
+            puzzle.package(),
+            std::env::consts::EXE_SUFFIX
+        ))
+    }
+}
+
+/// Parses the `Part1: ...` and `Time of Part 1, ...` lines printed by a day.
+///
+/// Answers printed on the lines after an empty `Part2:` (Ex: ascii art) are joined together up to the next answer,
+/// time or bench line. Only the first answer and time for each part is kept.
+pub fn parse_output(stdout: &str) -> [PartResult; 2] {
+    let answer_re = Regex::new(r"^Part ?([12])(?:[^:]*:)?\s*(.*)$").unwrap();
+    let time_re = Regex::new(r"^Time of Part ?([12])[^,]*, (.*)$").unwrap();
+    let bench_re = Regex::new(r"^Bench of Part ([12]), median (.*), p95 (.*), runs (\d+)$").unwrap();
+    let mut parts = [PartResult::default(), PartResult::default()];
+    // Part that is waiting for a multi-line answer
+    let mut multi_line: Option<(usize, Vec<&str>)> = None;
+    for line in stdout.lines() {
+        if let Some(caps) = time_re.captures(line) {
+            finish_multi_line(&mut parts, multi_line.take());
+            let part = &mut parts[caps[1].parse::<usize>().unwrap() - 1];
+            if part.time.is_none() {
+                part.time = parse_duration(&caps[2]);
+            }
+        } else if let Some(caps) = bench_re.captures(line) {
+            finish_multi_line(&mut parts, multi_line.take());
+            parts[caps[1].parse::<usize>().unwrap() - 1].bench = Some(BenchStats {
+                median: parse_duration(&caps[2]).unwrap_or_default(),
+                p95: parse_duration(&caps[3]).unwrap_or_default(),
+                runs: caps[4].parse().unwrap(),
+            });
+        } else if let Some(caps) = answer_re.captures(line) {
+            finish_multi_line(&mut parts, multi_line.take());
+            let index = caps[1].parse::<usize>().unwrap() - 1;
+            let answer = caps.get(2).unwrap().as_str().trim();
+            if answer.is_empty() {
+                multi_line = Some((index, vec![]));
+            } else if parts[index].answer.is_none() {
+                parts[index].answer = Some(answer.to_string());
+            }
+        } else if let Some((_, lines)) = multi_line.as_mut() {
+            lines.push(line);
+        }
+    }
+    finish_multi_line(&mut parts, multi_line);
+    parts
+}
+
+fn finish_multi_line(parts: &mut [PartResult; 2], multi_line: Option<(usize, Vec<&str>)>) {
+    if let Some((index, lines)) = multi_line {
+        if parts[index].answer.is_none() && !lines.is_empty() {
+            parts[index].answer = Some(lines.join("\n"));
+        }
+    }
+}
+
+/// Finds the message of a panic or an error returned from main in stderr
+pub fn parse_error(stderr: &str) -> String {
+    let lines = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect_vec();
+    let message = if let Some(error) = lines.iter().find_map(|line| line.strip_prefix("Error: ")) {
+        Some(error)
+    } else if let Some(index) = lines.iter().position(|line| line.contains("panicked at")) {
+        // The panic message is on the line after the location
+        lines.get(index + 1).copied()
+    } else {
+        lines.last().copied()
+    };
+    message.unwrap_or("exited with an error").to_string()
+}
+
+/// Parses the `Debug` output of a `Duration` Ex: 1.234ms
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = input.split_at(split);
+    let value = value.parse::<f64>().ok()?;
+    let nanos_per_unit = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(Duration::from_nanos((value * nanos_per_unit).round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_standard_output() {
+        let parts = parse_output("Part1: 102\nTime of Part 1, 1.5ms\nPart2: 94\nTime of Part 2, 250µs\n");
+        assert_eq!(parts[0].answer.as_deref(), Some("102"));
+        assert_eq!(parts[0].time, Some(Duration::from_micros(1500)));
+        assert_eq!(parts[1].answer.as_deref(), Some("94"));
+        assert_eq!(parts[1].time, Some(Duration::from_micros(250)));
+    }
+
+    #[test]
+    fn parse_other_output() {
+        let parts = parse_output("Part1(original algorithm): 21\nPart 1: 5 \nPart2: \n#..#\n.##.\n");
+        assert_eq!(parts[0].answer.as_deref(), Some("21"));
+        assert_eq!(parts[0].time, None);
+        assert_eq!(parts[1].answer.as_deref(), Some("#..#\n.##."));
+    }
+
+    #[test]
+    fn parse_with_debug_output() {
+        let parts = parse_output(
+            "brwrr\n  Pass\nPart1: 6\nTime of Part 1, 2ms\nPart2:\n#..#\nTime of Part 2, 1ms\nMemo of blinks, 3 hits, 5 misses, 5 cached\n",
+        );
+        assert_eq!(parts[0].answer.as_deref(), Some("6"));
+        assert_eq!(parts[1].answer.as_deref(), Some("#..#"));
+    }
+
+    #[test]
+    fn parse_bench_output() {
+        let parts = parse_output("Part2:\n#..#\nBench of Part 2, median 1.5ms, p95 2ms, runs 10\n");
+        assert_eq!(parts[1].answer.as_deref(), Some("#..#"));
+        assert_eq!(
+            parts[1].bench,
+            Some(BenchStats {
+                median: Duration::from_micros(1500),
+                p95: Duration::from_millis(2),
+                runs: 10
+            })
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let panic = "\nthread 'main' panicked at src/lib.rs:80:57:\nAOC_SESSION env var not found\nnote: run with `RUST_BACKTRACE=1`";
+        assert_eq!(parse_error(panic), "AOC_SESSION env var not found");
+        assert_eq!(
+            parse_error("Error: Day Not Implemented\n\nStack backtrace:\n   0: main"),
+            "Day Not Implemented"
+        );
+        assert_eq!(parse_error(""), "exited with an error");
+    }
+}
@@ -0,0 +1,71 @@
+// Submits an answer for a day, running the day first when no answer is given
+
+use common::*;
+
+use crate::{
+    registry::{Registry, Selection},
+    runner::Runner,
+};
+
+/// Submits the answer for the profile, correct answers are saved in the year's answers.toml
+pub fn submit(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    profile: Option<String>,
+    release: bool,
+) -> R<()> {
+    let puzzle = *registry
+        .select(Selection::Day(year, day))?
+        .first()
+        .ok_or_else(|| AdventOfCodeError::new(format!("{} day {} was not found", year, day)))?;
+    let mut store = InputStore::from_env();
+    if let Some(profile) = &profile {
+        store = store.with_profile(profile);
+    }
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let runner = Runner {
+                release,
+                part: Some(part),
+                bench: None,
+                visualize: None,
+            };
+            runner.build(&[puzzle])?;
+            let result = runner.run(puzzle, profile.as_deref())?;
+            if let Some(error) = result.error {
+                Err(AdventOfCodeError::new(format!("Failed to run: {}", error)))?
+            }
+            result.parts[part as usize - 1]
+                .answer
+                .clone()
+                .ok_or_else(|| AdventOfCodeError::new(format!("Part {} did not print an answer", part)))?
+        }
+    };
+    if answer.contains('\n') {
+        Err(AdventOfCodeError::new(format!(
+            "Answer spans multiple lines, read it and pass it as an argument:\n{}",
+            answer
+        )))?
+    }
+    println!(
+        "Submitting {} for {} day {} part {} ({})",
+        answer,
+        year,
+        day,
+        part,
+        store.profile()
+    );
+    let outcome = submit_answer(&store, &HttpProvider::for_store(&store)?, year, day, part, &answer)?;
+    println!("{}", outcome);
+    if outcome == Outcome::Correct {
+        let path = Answers::path(puzzle.workspace());
+        let mut answers = Answers::load(&path)?;
+        answers.set(day, part, store.profile(), answer.trim().to_string());
+        answers.save(path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// Checks the answers from running the days against the answers saved in each year's answers.toml
+
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use common::*;
+
+use crate::{runner::RunResult, table::Table};
+
+/// Prints how every answer compares to the saved one, returns an error if any answer changed.
+///
+/// With `record` set, answers that are not saved yet are added to the answers.toml files
+pub fn verify(results: &[RunResult], record: bool) -> R<()> {
+    let default_profile = InputStore::from_env().profile().to_string();
+    // Answers for each year folder, loaded as needed
+    let mut answers = BTreeMap::new();
+    let mut table = Table::new(["Year", "Day", "Profile", "Part 1", "Part 2", "Error"]);
+    let mut mismatches = 0;
+    for result in results {
+        let puzzle = &result.puzzle;
+        let profile = result.profile.as_deref().unwrap_or(&default_profile);
+        let (year_answers, changed) = match answers.entry(puzzle.workspace().to_path_buf()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert((Answers::load(Answers::path(puzzle.workspace()))?, false)),
+        };
+        let mut row = vec![puzzle.year.to_string(), puzzle.day.to_string(), profile.to_string()];
+        for (index, part) in result.parts.iter().enumerate() {
+            let part_number = index as u8 + 1;
+            let Some(answer) = part.answer.as_deref() else {
+                row.push(String::new());
+                continue;
+            };
+            let cell = match year_answers.verify(puzzle.day, part_number, profile, answer) {
+                Verification::Match => "ok".to_string(),
+                Verification::Mismatch { expected } => {
+                    mismatches += 1;
+                    format!("MISMATCH expected {} got {}", expected, answer)
+                }
+                Verification::Missing if record && result.error.is_none() => {
+                    year_answers.set(puzzle.day, part_number, profile, answer.to_string());
+                    *changed = true;
+                    "recorded".to_string()
+                }
+                Verification::Missing => "missing".to_string(),
+            };
+            row.push(cell);
+        }
+        row.push(result.error.clone().unwrap_or_default());
+        table.push(row);
+    }
+    print!("{}", table);
+    for (year_dir, (year_answers, changed)) in answers {
+        if changed {
+            year_answers.save(Answers::path(year_dir))?;
+        }
+    }
+    let errors = results.iter().filter(|r| r.error.is_some()).count();
+    if mismatches > 0 || errors > 0 {
+        Err(AdventOfCodeError::new(format!(
+            "{} answers did not match and {} solutions failed to run",
+            mismatches, errors
+        )))?
+    }
+    Ok(())
+}
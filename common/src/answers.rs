@@ -0,0 +1,274 @@
+// Accepted answers saved per year so old days can be checked after changing shared code
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_input_path, AdventOfCodeError, InputStore, Solution, R};
+
+/// Answers to both parts of a day for one profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl PartAnswers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part1 = Some(answer),
+            2 => self.part2 = Some(answer),
+            _ => panic!("There are only 2 parts, got part {}", part),
+        }
+    }
+}
+
+/// Accepted answers for a year, saved in `<year>/answers.toml` as
+///
+/// ```toml
+/// [day17.default]
+/// part1 = "102"
+/// part2 = "94"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    /// Day (Ex: day17) to profile to answers
+    days: BTreeMap<String, BTreeMap<String, PartAnswers>>,
+}
+
+/// Result of comparing an answer to the saved one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Match,
+    Mismatch { expected: String },
+    Missing,
+}
+
+impl Answers {
+    /// Where the answers for a year folder are saved
+    pub fn path<P>(year_dir: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        year_dir.as_ref().join("answers.toml")
+    }
+
+    /// Loads the answers, a missing file has no answers
+    pub fn load<P>(path: P) -> R<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if path.is_file() {
+            Ok(toml::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save<P>(&self, path: P) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: u8, profile: &str) -> Option<&str> {
+        self.days.get(&day_key(day))?.get(profile)?.get(part)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, profile: &str, answer: String) {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .entry(profile.to_string())
+            .or_default()
+            .set(part, answer);
+    }
+
+    /// Every profile with answers for the day
+    pub fn profiles(&self, day: u8) -> impl Iterator<Item = (&str, &PartAnswers)> {
+        self.days
+            .get(&day_key(day))
+            .into_iter()
+            .flatten()
+            .map(|(profile, answers)| (profile.as_str(), answers))
+    }
+
+    pub fn verify(&self, day: u8, part: u8, profile: &str, answer: &str) -> Verification {
+        match self.get(day, part, profile) {
+            Some(expected) if expected == answer => Verification::Match,
+            Some(expected) => Verification::Mismatch {
+                expected: expected.to_string(),
+            },
+            None => Verification::Missing,
+        }
+    }
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Runs a part of the day against the input of every profile with a saved answer and errors on any mismatch.
+///
+/// Profiles without a downloaded input are skipped, but it is an error when no answer could be checked so a test
+/// never passes without checking anything. Used by `answer_tests!`
+pub fn check_answers<D>(manifest_dir: &Path, part: u8) -> R<()>
+where
+    D: Solution,
+{
+    let legacy_input = manifest_dir.join("Input.txt");
+    let (year, day) = parse_input_path(&legacy_input)?;
+    let year_dir = manifest_dir.parent().ok_or(AdventOfCodeError::YearError)?;
+    let answers = Answers::load(Answers::path(year_dir))?;
+    let store = InputStore::from_env();
+    let mut mismatches = vec![];
+    let mut checked = 0;
+    for (profile, saved) in answers.profiles(day) {
+        let Some(expected) = saved.get(part) else {
+            continue;
+        };
+        let store = store.clone().with_profile(profile);
+        let path = if store.is_default_profile() && legacy_input.is_file() {
+            legacy_input.clone()
+        } else {
+            store.path(year, day)
+        };
+        if !path.is_file() {
+            continue;
+        }
+        let input = fs::read_to_string(path)?;
+        checked += 1;
+        let answer = match part {
+            1 => D::solve_part1(&input)?.to_string(),
+            _ => D::solve_part2(&input)?.to_string(),
+        };
+        if answer != expected {
+            mismatches.push(format!("{}: expected {} but got {}", profile, expected, answer));
+        }
+    }
+    if checked == 0 {
+        Err(AdventOfCodeError::new(format!(
+            "No saved answer for day {} part {} with a downloaded input, record one with `aoc verify {} {} --record`",
+            day, part, year, day
+        )))?
+    }
+    if !mismatches.is_empty() {
+        Err(AdventOfCodeError::new(mismatches.join("\n")))?
+    }
+    Ok(())
+}
+
+/// Generates tests checking both parts against the answers saved in the year's `answers.toml`.
+///
+/// They need the real inputs so they are ignored by default, run them with `cargo test -- --ignored`
+#[macro_export]
+macro_rules! answer_tests {
+    ($solution:ty) => {
+        #[test]
+        #[ignore = "needs the saved answers and the real input"]
+        fn p1_answers() {
+            common::check_answers::<$solution>(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), 1).unwrap();
+        }
+        #[test]
+        #[ignore = "needs the saved answers and the real input"]
+        fn p2_answers() {
+            common::check_answers::<$solution>(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), 2).unwrap();
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_file() {
+        let mut answers: Answers = toml::from_str(
+            r#"
+[day17.default]
+part1 = "102"
+
+[day17.alice]
+part1 = "5"
+part2 = "6"
+"#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(17, 1, "default"), Some("102"));
+        assert_eq!(answers.get(17, 2, "default"), None);
+        assert_eq!(answers.get(17, 2, "alice"), Some("6"));
+        assert_eq!(answers.verify(17, 1, "default", "102"), Verification::Match);
+        assert_eq!(
+            answers.verify(17, 1, "alice", "7"),
+            Verification::Mismatch { expected: "5".into() }
+        );
+        assert_eq!(answers.verify(3, 1, "alice", "7"), Verification::Missing);
+
+        answers.set(3, 2, "default", "#..\n.#.".to_string());
+        let round_trip: Answers = toml::from_str(&toml::to_string(&answers).unwrap()).unwrap();
+        assert_eq!(round_trip, answers);
+        assert_eq!(
+            answers.profiles(17).map(|(p, _)| p).collect::<Vec<_>>(),
+            ["alice", "default"]
+        );
+    }
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input<'a> = &'a str;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> R<Self::Input<'_>> {
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+            Ok(input.lines().count())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn check_saved_answers() {
+        let year_dir = std::env::temp_dir().join(format!("aoc-answers-{}/2023", std::process::id()));
+        let day_dir = year_dir.join("day05");
+        fs::create_dir_all(&day_dir).unwrap();
+        // Nothing saved, the test must not pass without checking anything
+        let error = check_answers::<Lines>(&day_dir, 1).unwrap_err().to_string();
+        assert!(error.contains("No saved answer for day 5 part 1"), "{}", error);
+
+        fs::write(day_dir.join("Input.txt"), "a\nb\nc").unwrap();
+        fs::write(
+            Answers::path(&year_dir),
+            "[day05.default]\npart1 = \"3\"\npart2 = \"4\"\n",
+        )
+        .unwrap();
+        assert!(check_answers::<Lines>(&day_dir, 1).is_ok());
+        let error = check_answers::<Lines>(&day_dir, 2).unwrap_err().to_string();
+        assert!(error.ends_with("default: expected 4 but got 5"), "{}", error);
+        fs::remove_dir_all(year_dir.parent().unwrap()).unwrap();
+    }
+}
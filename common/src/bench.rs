@@ -0,0 +1,79 @@
+// Timing a part many times to get numbers that can be compared between runs
+
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// Timings from running something many times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub median: Duration,
+    pub p95: Duration,
+    pub runs: usize,
+}
+
+impl BenchStats {
+    /// Stats for the given timings, None when there are none
+    pub fn from_times(mut times: Vec<Duration>) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+        times.sort();
+        // Nearest rank, Ex: the 95th of 100 times or the 10th of 10
+        let rank = |percent: usize| times[(times.len() * percent).div_ceil(100) - 1];
+        Some(Self {
+            median: rank(50),
+            p95: rank(95),
+            runs: times.len(),
+        })
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "median {:?}, p95 {:?}, runs {}", self.median, self.p95, self.runs)
+    }
+}
+
+/// Runs `f` a few times to warm up and then times `runs` more runs with a monotonic clock
+pub fn bench<F, T>(runs: usize, mut f: F) -> BenchStats
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..(runs / 10).max(1) {
+        std::hint::black_box(f());
+    }
+    let times = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    BenchStats::from_times(times).expect("There is always at least one run")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let times = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = BenchStats::from_times(times).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.runs, 20);
+        assert_eq!(
+            BenchStats::from_times(vec![Duration::from_millis(3)]).unwrap().p95,
+            Duration::from_millis(3)
+        );
+        assert_eq!(BenchStats::from_times(vec![]), None);
+
+        let mut calls = 0;
+        assert_eq!(bench(20, || calls += 1).runs, 20);
+        // 2 warm up runs
+        assert_eq!(calls, 22);
+    }
+}
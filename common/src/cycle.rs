@@ -0,0 +1,223 @@
+// Finding where a repeating process loops so it can skip ahead to a huge step count
+
+use std::{collections::HashMap, hash::Hash};
+
+use num::PrimInt;
+
+/// A sequence that repeats every `len` steps once `start` steps have run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Step the loop first starts at (mu)
+    pub start: usize,
+    /// Steps in one loop (lambda)
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Step before the end of the first loop that has the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Value at step `n` of something that grows by the same amount each loop, Ex: the height of a tower.
+    ///
+    /// `values[i]` is the value after `i` steps and must cover at least `start + len` steps
+    pub fn extrapolate<T>(&self, values: &[T], n: usize) -> T
+    where
+        T: PrimInt,
+    {
+        if n < values.len() {
+            return values[n];
+        }
+        let per_loop = values[self.start + self.len] - values[self.start];
+        let loops = T::from((n - self.start) / self.len).expect("Loops fit in the value type");
+        values[self.reduce(n)] + per_loop * loops
+    }
+}
+
+/// Floyd's tortoise and hare, only keeps two states in memory
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+    // The distance from the start to the loop is the same as from the meeting point to the loop
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    let mut len = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Brent's algorithm, also two states in memory but fewer calls to `step` than `floyd`
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+    // Move the hare a loop ahead then walk both until they meet at the start
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Remembers the step each key was seen at to spot the loop in a simulation that is run by hand.
+///
+/// The key must capture everything that decides the next steps, Ex: a piece index and the top of a board
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    seen: HashMap<K, usize>,
+    steps: usize,
+}
+
+impl<K> Default for CycleDetector<K> {
+    fn default() -> Self {
+        Self {
+            seen: HashMap::new(),
+            steps: 0,
+        }
+    }
+}
+
+impl<K> CycleDetector<K>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the key for the next step starting with step 0, returns the cycle once a key repeats
+    pub fn push(&mut self, key: K) -> Option<Cycle> {
+        let step = self.steps;
+        self.steps += 1;
+        self.seen.insert(key, step).map(|start| Cycle {
+            start,
+            len: step - start,
+        })
+    }
+}
+
+/// Runs `step` until a state repeats, returns the cycle along with every state up to the end of the first loop
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut detector = CycleDetector::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(cycle) = detector.push(state.clone()) {
+            return (cycle, states);
+        }
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State after `n` steps, skipping the repeats once the loop is found
+pub fn state_at<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut detector = CycleDetector::new();
+    let mut states = vec![];
+    let mut state = initial;
+    while states.len() < n {
+        if let Some(cycle) = detector.push(state.clone()) {
+            return states.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4 then loops 5..=9
+    fn step(x: &usize) -> usize {
+        if *x == 9 {
+            5
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let expected = Cycle { start: 5, len: 5 };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, (0..10).collect::<Vec<_>>());
+        assert_eq!(floyd(7, step), Cycle { start: 0, len: 5 });
+        assert_eq!(brent(7, step), Cycle { start: 0, len: 5 });
+
+        assert_eq!(state_at(0, step, 3), 3);
+        assert_eq!(state_at(0, step, 10), 5);
+        assert_eq!(state_at(0, step, 1_000_000_000_000), 5);
+        assert_eq!(state_at(0, step, 1_000_000_000_003), 8);
+        assert_eq!(expected.reduce(1_000_000_000_003), 8);
+    }
+
+    #[test]
+    fn extrapolate() {
+        // Grows by 1 during the lead in then by 10 each loop of 2
+        let values: Vec<u64> = vec![0, 1, 2, 5, 12, 15, 22];
+        let cycle = Cycle { start: 2, len: 2 };
+        assert_eq!(cycle.extrapolate(&values, 4), 12);
+        assert_eq!(cycle.extrapolate(&values, 7), 25);
+        assert_eq!(cycle.extrapolate(&values, 8), 32);
+        assert_eq!(cycle.extrapolate(&values, 2 + 2 * 1_000), 2 + 10 * 1_000);
+    }
+}
@@ -0,0 +1,213 @@
+// Random puzzle inputs and checks of a fast solution against a slow one that is easier to trust, Ex: a brute force
+
+use std::fmt::Debug;
+
+use rand::SeedableRng;
+/// Re-exported so generators can take `&mut StdRng` and call `gen_range` with just `use common::*`
+pub use rand::{rngs::StdRng, Rng};
+
+use crate::{AdventOfCodeError, R};
+
+/// Most shrinking steps tried before giving up with the smallest failing input found so far
+const MAX_SHRINKS: usize = 1000;
+
+/// Makes random puzzle input for a day so tests have more to run on than the example and the real input
+pub trait InputGenerator {
+    /// Valid puzzle text, every random choice comes from `rng`
+    fn generate(&self, rng: &mut StdRng) -> String;
+
+    /// The same seed always gives the same text, Ex: to reproduce a failing case
+    fn generate_seeded(&self, seed: u64) -> String {
+        self.generate(&mut StdRng::seed_from_u64(seed))
+    }
+}
+
+/// Function of an input boxed so the reference, fast and shrink closures can all be stored
+type BoxedFn<'a, I, O> = Box<dyn Fn(&I) -> O + 'a>;
+
+/// A reference and a fast implementation that should always agree along with how to make inputs for them
+pub struct DiffTest<'a, I, O> {
+    reference: BoxedFn<'a, I, O>,
+    fast: BoxedFn<'a, I, O>,
+    generate: Box<dyn Fn(&mut StdRng) -> I + 'a>,
+    shrink: BoxedFn<'a, I, Vec<I>>,
+    cases: usize,
+    seed: u64,
+}
+
+impl<'a, I, O> DiffTest<'a, I, O>
+where
+    I: Debug,
+    O: PartialEq + Debug,
+{
+    /// Defaults to 200 cases from seed 0 with no shrinking
+    pub fn new<FR, FF, G>(reference: FR, fast: FF, generate: G) -> Self
+    where
+        FR: Fn(&I) -> O + 'a,
+        FF: Fn(&I) -> O + 'a,
+        G: Fn(&mut StdRng) -> I + 'a,
+    {
+        Self {
+            reference: Box::new(reference),
+            fast: Box::new(fast),
+            generate: Box::new(generate),
+            shrink: Box::new(|_| vec![]),
+            cases: 200,
+            seed: 0,
+        }
+    }
+
+    /// Smaller versions of an input to try when it fails, Ex: `shrink_lines`. The first that still fails is kept and
+    /// shrunk again
+    pub fn with_shrink<S>(mut self, shrink: S) -> Self
+    where
+        S: Fn(&I) -> Vec<I> + 'a,
+    {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    pub fn with_cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Both answers when they aren't the same
+    fn disagreement(&self, input: &I) -> Option<(O, O)> {
+        let (reference, fast) = ((self.reference)(input), (self.fast)(input));
+        (reference != fast).then_some((reference, fast))
+    }
+
+    /// Runs every case, the error has the smallest input found where the two disagree and both answers
+    pub fn run(&self) -> R<()> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        for case in 0..self.cases {
+            let mut input = (self.generate)(&mut rng);
+            let Some(mut answers) = self.disagreement(&input) else {
+                continue;
+            };
+            let mut shrinks = 0;
+            'shrinking: while shrinks < MAX_SHRINKS {
+                for smaller in (self.shrink)(&input) {
+                    if let Some(smaller_answers) = self.disagreement(&smaller) {
+                        (input, answers, shrinks) = (smaller, smaller_answers, shrinks + 1);
+                        continue 'shrinking;
+                    }
+                }
+                break;
+            }
+            Err(AdventOfCodeError::new(format!(
+                "Case {} of seed {} disagrees, shrunk {} times to:\n{:?}\nreference: {:?}\nfast: {:?}",
+                case, self.seed, shrinks, input, answers.0, answers.1
+            )))?
+        }
+        Ok(())
+    }
+}
+
+impl<'a, O> DiffTest<'a, String, O>
+where
+    O: PartialEq + Debug,
+{
+    /// Compares two solutions of a day on text from its generator
+    pub fn for_generator<FR, FF, G>(reference: FR, fast: FF, generator: G) -> Self
+    where
+        FR: Fn(&String) -> O + 'a,
+        FF: Fn(&String) -> O + 'a,
+        G: InputGenerator + 'a,
+    {
+        Self::new(reference, fast, move |rng| generator.generate(rng))
+    }
+}
+
+/// Lists with items left out, the two halves first to shrink quickly then each item on its own
+pub fn shrink_vec<T>(items: &[T]) -> Vec<Vec<T>>
+where
+    T: Clone,
+{
+    let mut smaller = vec![];
+    if items.len() > 1 {
+        let (first, second) = items.split_at(items.len() / 2);
+        smaller.push(first.to_vec());
+        smaller.push(second.to_vec());
+    }
+    for i in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(i);
+        smaller.push(without);
+    }
+    smaller
+}
+
+/// Puzzle text with lines left out, for inputs where each line stands on its own
+pub fn shrink_lines(input: &str) -> Vec<String> {
+    shrink_vec(&input.lines().collect::<Vec<_>>())
+        .into_iter()
+        .map(|lines| lines.join("\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn random_list(rng: &mut StdRng) -> Vec<u32> {
+        (0..rng.gen_range(0..20)).map(|_| rng.gen_range(0..100)).collect()
+    }
+
+    #[test]
+    fn agree() {
+        let sum = DiffTest::new(
+            |&n: &u64| (1..=n).sum::<u64>(),
+            |&n| n * (n + 1) / 2,
+            |rng| rng.gen_range(0..1000),
+        );
+        assert!(sum.run().is_ok());
+    }
+
+    #[test]
+    fn shrinks_failures() {
+        // Forgets to look at the last item
+        let max = DiffTest::new(
+            |items: &Vec<u32>| items.iter().max().copied(),
+            |items| items[..items.len().saturating_sub(1)].iter().max().copied(),
+            random_list,
+        )
+        .with_shrink(|items| shrink_vec(items));
+        let error = max.run().unwrap_err().to_string();
+        // A single item is the smallest list they disagree on
+        let input = error.lines().nth(1).unwrap();
+        assert!(input.starts_with('[') && !input.contains(','), "{}", error);
+        assert!(error.contains("fast: None"));
+
+        assert_eq!(shrink_lines("a\nb\nc"), ["a", "b\nc", "b\nc", "a\nc", "a\nb"]);
+    }
+
+    /// One number per line
+    struct Numbers;
+
+    impl InputGenerator for Numbers {
+        fn generate(&self, rng: &mut StdRng) -> String {
+            random_list(rng).iter().join("\n")
+        }
+    }
+
+    #[test]
+    fn generators() {
+        assert_eq!(Numbers.generate_seeded(7), Numbers.generate_seeded(7));
+        let count = |input: &String| input.lines().count();
+        let test = DiffTest::for_generator(
+            count,
+            |input| input.split('\n').filter(|l| !l.is_empty()).count(),
+            Numbers,
+        );
+        assert!(test.run().is_ok());
+    }
+}
@@ -0,0 +1,357 @@
+// Writes graphs as Graphviz DOT to be drawn with Ex: `dot -Tsvg day23.dot -o day23.svg`
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    fs,
+    hash::Hash,
+    path::Path,
+};
+
+use petgraph::visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef};
+
+use crate::R;
+
+/// Colour of highlighted nodes and edges
+const HIGHLIGHT: &str = "red";
+
+/// `key="value"` pairs of a node, edge or the whole graph, Ex: `label` or `shape`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Attributes(Vec<(String, String)>);
+
+impl Attributes {
+    fn set(&mut self, key: &str, value: &str) {
+        match self.0.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.0.push((key.to_string(), value.to_string())),
+        }
+    }
+}
+
+impl Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, " [")?;
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}=\"{}\"", key, escape(value))?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Escapes quotes and backslashes, newlines become DOT's `\n`
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Graph written as DOT, nodes can be keyed by anything hashable and are named `n0`, `n1`... in the order they are
+/// added
+#[derive(Debug, Clone)]
+pub struct DotGraph<N> {
+    directed: bool,
+    /// Attributes of the whole graph, Ex: `rankdir`
+    graph: Attributes,
+    /// Attributes every node starts with, Ex: `shape`
+    node_defaults: Attributes,
+    nodes: Vec<Attributes>,
+    ids: HashMap<N, usize>,
+    edges: Vec<(usize, usize, Attributes)>,
+}
+
+impl<N> DotGraph<N>
+where
+    N: Hash + Eq + Clone,
+{
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            graph: Attributes::default(),
+            node_defaults: Attributes::default(),
+            nodes: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    /// Attribute of the whole graph, Ex: `("rankdir", "LR")` to draw it left to right
+    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.graph.set(key, value);
+        self
+    }
+
+    /// Attribute of every node unless the node sets its own, Ex: `("shape", "record")`
+    pub fn with_node_attribute(mut self, key: &str, value: &str) -> Self {
+        self.node_defaults.set(key, value);
+        self
+    }
+
+    fn node_id(&mut self, node: N) -> usize {
+        let next = self.nodes.len();
+        let id = *self.ids.entry(node).or_insert(next);
+        if id == next {
+            self.nodes.push(Attributes::default());
+        }
+        id
+    }
+
+    /// Adds the node if it is new and sets its label
+    pub fn add_node<S>(&mut self, node: N, label: S)
+    where
+        S: AsRef<str>,
+    {
+        let id = self.node_id(node);
+        self.nodes[id].set("label", label.as_ref());
+    }
+
+    /// Adds an edge, nodes that haven't been added yet are added without a label
+    pub fn add_edge(&mut self, from: N, to: N, label: Option<&str>) {
+        let (from, to) = (self.node_id(from), self.node_id(to));
+        let mut attributes = Attributes::default();
+        if let Some(label) = label {
+            attributes.set("label", label);
+        }
+        self.edges.push((from, to, attributes));
+    }
+
+    /// Sets any attribute of a node that has been added, Ex: `("color", "blue")`
+    pub fn set_node_attribute(&mut self, node: &N, key: &str, value: &str) {
+        if let Some(&id) = self.ids.get(node) {
+            self.nodes[id].set(key, value);
+        }
+    }
+
+    /// Sets any attribute of every edge between the nodes, the direction doesn't matter in an undirected graph
+    pub fn set_edge_attribute(&mut self, from: &N, to: &N, key: &str, value: &str) {
+        let (Some(&from), Some(&to)) = (self.ids.get(from), self.ids.get(to)) else {
+            return;
+        };
+        let directed = self.directed;
+        for (a, b, attributes) in &mut self.edges {
+            if (*a, *b) == (from, to) || (!directed && (*a, *b) == (to, from)) {
+                attributes.set(key, value);
+            }
+        }
+    }
+
+    /// Draws the nodes in red
+    pub fn highlight_nodes<I>(&mut self, nodes: I)
+    where
+        I: IntoIterator<Item = N>,
+    {
+        for node in nodes {
+            self.set_node_attribute(&node, "color", HIGHLIGHT);
+        }
+    }
+
+    /// Draws the edges in red and thicker, Ex: the edges to cut
+    pub fn highlight_edges<I>(&mut self, edges: I)
+    where
+        I: IntoIterator<Item = (N, N)>,
+    {
+        for (from, to) in edges {
+            self.set_edge_attribute(&from, &to, "color", HIGHLIGHT);
+            self.set_edge_attribute(&from, &to, "penwidth", "2");
+        }
+    }
+
+    /// Highlights the nodes along the path and the edges between them, Ex: the longest walk through a maze
+    pub fn highlight_path(&mut self, path: &[N]) {
+        self.highlight_nodes(path.iter().cloned());
+        self.highlight_edges(path.windows(2).map(|pair| (pair[0].clone(), pair[1].clone())));
+    }
+
+    pub fn save<P>(&self, path: P) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl DotGraph<usize> {
+    /// Any petgraph graph keyed by node index, the closures give the labels and edges can go without one
+    pub fn from_petgraph<G, FN, FE>(graph: G, mut node_label: FN, mut edge_label: FE) -> Self
+    where
+        G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+        FN: FnMut(G::NodeRef) -> String,
+        FE: FnMut(G::EdgeRef) -> Option<String>,
+    {
+        let mut dot = Self::new(graph.is_directed());
+        for node in graph.node_references() {
+            dot.add_node(graph.to_index(node.id()), node_label(node));
+        }
+        for edge in graph.edge_references() {
+            let label = edge_label(edge);
+            dot.add_edge(
+                graph.to_index(edge.source()),
+                graph.to_index(edge.target()),
+                label.as_deref(),
+            );
+        }
+        dot
+    }
+}
+
+impl<N> DotGraph<N>
+where
+    N: Hash + Eq + Clone + Display,
+{
+    /// Adjacency lists like `HashMap<&str, Vec<&str>>`, nodes are labelled with their `Display`. When undirected
+    /// an edge listed from both ends is only drawn once
+    pub fn from_adjacency<'a, I, V>(adjacency: I, directed: bool) -> Self
+    where
+        N: 'a,
+        V: 'a,
+        I: IntoIterator<Item = (&'a N, &'a V)>,
+        &'a V: IntoIterator<Item = &'a N>,
+    {
+        let mut dot = Self::new(directed);
+        let mut seen = HashSet::new();
+        for (node, neighbors) in adjacency {
+            dot.add_node(node.clone(), node.to_string());
+            for neighbor in neighbors {
+                dot.add_node(neighbor.clone(), neighbor.to_string());
+                if directed || !seen.contains(&(neighbor, node)) {
+                    seen.insert((node, neighbor));
+                    dot.add_edge(node.clone(), neighbor.clone(), None);
+                }
+            }
+        }
+        dot
+    }
+}
+
+impl<K> DotGraph<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Memoisation table drawn left to right, Ex: `(index, group, count) -> ways`. `edges` gives the entries each
+    /// one was worked out from with an optional label, only entries in the table are drawn
+    pub fn from_memo<'a, V, I, FN, FE>(table: I, mut node_label: FN, mut edges: FE) -> Self
+    where
+        K: 'a,
+        V: 'a,
+        I: IntoIterator<Item = (&'a K, &'a V)>,
+        FN: FnMut(&K, &V) -> String,
+        FE: FnMut(&K, &V) -> Vec<(K, Option<String>)>,
+    {
+        let mut dot = Self::directed().with_attribute("rankdir", "LR");
+        let table = table.into_iter().collect::<Vec<_>>();
+        for &(key, value) in &table {
+            dot.add_node(key.clone(), node_label(key, value));
+        }
+        for &(key, value) in &table {
+            for (next, label) in edges(key, value) {
+                if dot.ids.contains_key(&next) {
+                    dot.add_edge(key.clone(), next, label.as_deref());
+                }
+            }
+        }
+        dot
+    }
+}
+
+impl<N> Display for DotGraph<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{} {{", kind)?;
+        for (key, value) in &self.graph.0 {
+            writeln!(f, "    {}=\"{}\";", key, escape(value))?;
+        }
+        if !self.node_defaults.0.is_empty() {
+            writeln!(f, "    node{};", self.node_defaults)?;
+        }
+        for (id, attributes) in self.nodes.iter().enumerate() {
+            writeln!(f, "    n{}{};", id, attributes)?;
+        }
+        for (from, to, attributes) in &self.edges {
+            writeln!(f, "    n{} {} n{}{};", from, arrow, to, attributes)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use petgraph::{graph::UnGraph, Graph};
+
+    use super::*;
+
+    #[test]
+    fn petgraph_with_path() {
+        let mut graph = Graph::<&str, u32>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c \"quoted\"");
+        graph.add_edge(a, b, 3);
+        graph.add_edge(b, c, 4);
+        graph.add_edge(a, c, 10);
+        let mut dot = DotGraph::from_petgraph(&graph, |(_, name)| name.to_string(), |e| Some(e.weight().to_string()));
+        dot.highlight_path(&[a.index(), b.index(), c.index()]);
+        assert_eq!(
+            dot.to_string(),
+            r#"digraph {
+    n0 [label="a", color="red"];
+    n1 [label="b", color="red"];
+    n2 [label="c \"quoted\"", color="red"];
+    n0 -> n1 [label="3", color="red", penwidth="2"];
+    n1 -> n2 [label="4", color="red", penwidth="2"];
+    n0 -> n2 [label="10"];
+}"#
+        );
+
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let mut dot = DotGraph::from_petgraph(&graph, |(i, _)| i.index().to_string(), |_| None);
+        // Undirected edges match either way round
+        dot.highlight_edges([(2, 1)]);
+        assert!(dot.to_string().starts_with("graph {"));
+        assert!(dot.to_string().contains("n1 -- n2 [color=\"red\", penwidth=\"2\"];"));
+    }
+
+    #[test]
+    fn adjacency() {
+        let adjacency = HashMap::from([("a", vec!["b"]), ("b", vec!["a"])]);
+        let dot = DotGraph::from_adjacency(&adjacency, false);
+        assert_eq!(dot.to_string().matches("--").count(), 1);
+        let dot = DotGraph::from_adjacency(&adjacency, true);
+        assert_eq!(dot.to_string().matches("->").count(), 2);
+    }
+
+    #[test]
+    fn memo_table() {
+        // Ways to climb n stairs taking 1 or 2 at a time
+        let table: BTreeMap<usize, usize> = BTreeMap::from([(0, 1), (1, 1), (2, 2), (3, 3)]);
+        let dot = DotGraph::from_memo(
+            &table,
+            |n, ways| format!("{}|ways: {}", n, ways),
+            |&n, _| vec![(n.wrapping_sub(1), Some("1".to_string())), (n.wrapping_sub(2), None)],
+        )
+        .with_node_attribute("shape", "record");
+        let text = dot.to_string();
+        assert!(text.contains("    rankdir=\"LR\";\n    node [shape=\"record\"];\n"));
+        assert!(text.contains("n3 [label=\"3|ways: 3\"];"));
+        assert!(text.contains("n3 -> n2 [label=\"1\"];\n    n3 -> n1;"));
+        // Entries below 0 and 1 aren't in the table so aren't drawn
+        assert_eq!(text.matches("->").count(), 5);
+    }
+}
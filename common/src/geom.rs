@@ -0,0 +1,539 @@
+// Points and directions for moving around grids and space
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+use num::{Signed, Zero};
+
+use crate::Grid;
+
+/// A point on a plane, on a grid `x` is the col and `y` is the row so `y` grows going down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Distance between two numbers, works for unsigned numbers too
+fn abs_diff<T>(a: T, b: T) -> T
+where
+    T: Copy + Ord + Sub<Output = T>,
+{
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Distance moving only up/down/left/right
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Distance when diagonal moves are allowed
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Signed + Copy,
+{
+    /// Point one step in the direction
+    pub fn step<D>(self, dir: D) -> Self
+    where
+        D: Heading,
+    {
+        self + dir.delta()
+    }
+
+    /// Point `steps` steps in the direction
+    pub fn step_n<D>(self, dir: D, steps: T) -> Self
+    where
+        D: Heading,
+    {
+        self + dir.delta() * steps
+    }
+
+    /// Up, right, down and left of the point
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// Every point around this one including diagonals
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Signed + Copy,
+{
+    /// Points sharing a face with this one
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        let (one, zero) = (T::one(), T::zero());
+        [
+            Self::new(one, zero, zero),
+            Self::new(-one, zero, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, -one, zero),
+            Self::new(zero, zero, one),
+            Self::new(zero, zero, -one),
+        ]
+        .into_iter()
+        .map(move |delta| self + delta)
+    }
+}
+
+impl Point2<usize> {
+    /// Point one step in the direction, None when it would go below 0
+    pub fn checked_step<D>(self, dir: D) -> Option<Self>
+    where
+        D: Heading,
+    {
+        let delta = dir.delta::<isize>();
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+
+    /// Converts to a signed point, Ex: to step off of the edge of a grid
+    pub fn signed(self) -> Point2<isize> {
+        Point2::new(self.x as isize, self.y as isize)
+    }
+}
+
+impl Point2<isize> {
+    /// Converts to an unsigned point, None if either value is negative
+    pub fn unsigned(self) -> Option<Point2<usize>> {
+        Some(Point2::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T> Add for Point2<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> Sub for Point2<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> Mul<T> for Point2<T>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T> Neg for Point2<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> AddAssign for Point2<T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T> SubAssign for Point2<T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> Add for Point3<T>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T> Sub for Point3<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T> Mul<T> for Point3<T>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T> Neg for Point3<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> AddAssign for Point3<T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T> SubAssign for Point3<T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> Display for Point2<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> Display for Point3<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Something that moves a point by one step
+pub trait Heading: Copy {
+    /// How a point changes moving one step, `y` grows going down
+    fn delta<T>(self) -> Point2<T>
+    where
+        T: Signed + Copy;
+}
+
+/// The 4 directions in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+}
+
+impl Heading for Dir4 {
+    fn delta<T>(self) -> Point2<T>
+    where
+        T: Signed + Copy,
+    {
+        let (one, zero) = (T::one(), T::zero());
+        match self {
+            Dir4::Up => Point2::new(zero, -one),
+            Dir4::Right => Point2::new(one, zero),
+            Dir4::Down => Point2::new(zero, one),
+            Dir4::Left => Point2::new(-one, zero),
+        }
+    }
+}
+
+/// The 8 directions including diagonals in clockwise order, turning moves 45 degrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Heading for Dir8 {
+    fn delta<T>(self) -> Point2<T>
+    where
+        T: Signed + Copy,
+    {
+        let (one, zero) = (T::one(), T::zero());
+        match self {
+            Dir8::Up => Point2::new(zero, -one),
+            Dir8::UpRight => Point2::new(one, -one),
+            Dir8::Right => Point2::new(one, zero),
+            Dir8::DownRight => Point2::new(one, one),
+            Dir8::Down => Point2::new(zero, one),
+            Dir8::DownLeft => Point2::new(-one, one),
+            Dir8::Left => Point2::new(-one, zero),
+            Dir8::UpLeft => Point2::new(-one, -one),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+impl<T> Grid<T> {
+    /// Cell at the point, None when it is outside of the grid
+    pub fn at(&self, point: Point2<isize>) -> Option<&T> {
+        self.get_signed(point.y, point.x)
+    }
+
+    pub fn at_mut(&mut self, point: Point2<isize>) -> Option<&mut T> {
+        let index = self.index(point.y, point.x)?;
+        Some(&mut self.inner[index])
+    }
+
+    /// Point of an offset into the cells
+    pub fn index_to_point(&self, index: usize) -> Point2<usize> {
+        let (row, col) = self.index_to_row_col(index);
+        Point2::new(col, row)
+    }
+
+    /// Point of the first cell matching the predicate
+    pub fn position_point<F>(&self, f: F) -> Option<Point2<usize>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.inner.iter().position(f).map(|index| self.index_to_point(index))
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    /// Cell at the point, panics outside of the grid
+    fn index(&self, point: Point2<usize>) -> &T {
+        &self[(point.y, point.x)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point2<usize>) -> &mut T {
+        &mut self[(point.y, point.x)]
+    }
+}
+
+impl<T> Zero for Point2<T>
+where
+    T: Zero,
+{
+    fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 7));
+        assert_eq!(a - b, Point2::new(4, -3));
+        assert_eq!(-a * 2, Point2::new(-2, -4));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(Point2::new(5usize, 1).manhattan(&Point2::new(2, 3)), 5);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(&Point3::new(0, 0, 0)), 6);
+        assert_eq!(Point3::new(0, 0, 0).neighbors6().count(), 6);
+        assert_eq!(
+            Point2::new(0, 0).neighbors4().collect::<Vec<_>>(),
+            [(0, -1), (1, 0), (0, 1), (-1, 0)].map(Point2::from)
+        );
+        assert_eq!(
+            Point2::new(0, 0).neighbors8().filter(|p| p.x != 0 && p.y != 0).count(),
+            4
+        );
+        assert_eq!(Point2::new(0usize, 3).checked_step(Dir4::Left), None);
+        assert_eq!(Point2::new(0usize, 3).checked_step(Dir4::Up), Some(Point2::new(0, 2)));
+        assert_eq!(Point2::new(0, 0).step_n(Dir8::DownLeft, 3), Point2::new(-3, 3));
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+        for dir in Dir4::ALL {
+            assert_eq!(dir.delta::<i32>(), Dir8::from(dir).delta());
+            assert_eq!(dir.opposite().delta::<i32>(), -dir.delta());
+        }
+    }
+
+    #[test]
+    fn grid_points() {
+        let mut grid = Grid::new("ab\ncd");
+        assert_eq!(grid[Point2::new(1, 0)], 'b');
+        assert_eq!(grid.at(Point2::new(0, 1)), Some(&'c'));
+        assert_eq!(grid.at(Point2::new(-1, 1)), None);
+        *grid.at_mut(Point2::new(1, 1)).unwrap() = '#';
+        assert_eq!(grid.position_point(|&c| c == '#'), Some(Point2::new(1, 1)));
+    }
+}
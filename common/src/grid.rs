@@ -1,36 +1,113 @@
 // Helper for parsing the text input as a grid
 
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
 
-use anyhow::{bail, Error};
+use anyhow::bail;
+use itertools::Itertools;
+use petgraph::{graph::Graph, EdgeType};
 
-/// A grid representation of the input
-#[derive(Debug, Clone)]
-pub struct Grid {
-    /// The raw text input
-    pub inner: String,
+use crate::{AdventOfCodeError, Dir4, Point2, R};
+
+/// A grid representation of the input, `Grid` on its own is a grid of chars
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T = char> {
+    /// The cells row by row
+    pub inner: Vec<T>,
     /// Rows in the grid
     pub rows: usize,
     /// Cols in the grid
     pub cols: usize,
 }
 
-impl Grid {
+impl Grid<char> {
     /// Creates a new grid, assumes all rows and cols are same size
     pub fn new<S>(input: S) -> Self
     where
         S: Into<String>,
     {
-        let input = input.into();
-        let cols = input.lines().next().unwrap().len();
-        let rows = input.lines().count();
-        let inner = input.replace('\n', "");
-        Self { inner, rows, cols }
+        Self::parse(&input.into(), |c| c)
+    }
+}
+
+impl Grid<u8> {
+    /// Grid of single digit numbers Ex: heights or costs
+    pub fn parse_digits(input: &str) -> R<Self> {
+        Self::try_parse(input, |c| {
+            Ok(c.to_digit(10)
+                .ok_or_else(|| AdventOfCodeError::new(format!("{} is not a digit", c)))? as u8)
+        })
     }
 }
 
-impl Grid {
-    /// Converts an x/y index into an offset into the string
+impl<T> Grid<T> {
+    /// Creates a grid from the cells row by row
+    pub fn from_vec(inner: Vec<T>, cols: usize) -> Self {
+        assert!(
+            cols > 0 && inner.len().is_multiple_of(cols),
+            "Cells do not fill every row"
+        );
+        let rows = inner.len() / cols;
+        Self { inner, rows, cols }
+    }
+
+    /// Grid with every cell set to the same value
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            inner: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Converts each char of the text grid, assumes all rows are the same size
+    pub fn parse<F>(input: &str, mut f: F) -> Self
+    where
+        F: FnMut(char) -> T,
+    {
+        Self::try_parse(input, |c| Ok(f(c))).expect("Every row should be the same size")
+    }
+
+    /// Converts each char of the text grid, errors if a conversion fails or the rows are not the same size
+    pub fn try_parse<F>(input: &str, mut f: F) -> R<Self>
+    where
+        F: FnMut(char) -> R<T>,
+    {
+        let cols = input.lines().next().map(|x| x.chars().count()).unwrap_or(0);
+        let mut inner = vec![];
+        let mut rows = 0;
+        for line in input.lines() {
+            let start = inner.len();
+            for c in line.chars() {
+                inner.push(f(c)?);
+            }
+            if inner.len() - start != cols {
+                bail!("Row {} has {} cols, expected {}", rows, inner.len() - start, cols)
+            }
+            rows += 1;
+        }
+        Ok(Self { inner, rows, cols })
+    }
+
+    /// Converts every cell into a new grid of the same size
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            inner: self.inner.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Converts an x/y index into an offset into the cells
     #[inline]
     pub fn index(&self, row: isize, col: isize) -> Option<usize> {
         if row < 0 || col < 0 || row >= self.rows as isize || col >= self.cols as isize {
@@ -41,76 +118,373 @@ impl Grid {
             Some(index)
         }
     }
-    /// Gets a char from the string
+
+    /// Converts an offset into the cells to a x/y
     #[inline]
-    pub fn get_char(&self, row: usize, col: usize) -> Option<char> {
-        self.index(row as isize, col as isize)
-            .map(|index| self.inner.as_bytes()[index] as char)
+    pub fn index_to_row_col(&self, index: usize) -> (usize, usize) {
+        (index / self.cols, index % self.cols)
     }
-    /// Gets a char from the string
+
     #[inline]
-    pub fn get_char_signed(&self, row: isize, col: isize) -> Option<char> {
-        self.index(row, col).map(|index| self.inner.as_bytes()[index] as char)
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.get_signed(row as isize, col as isize)
     }
-    /// Converts an offset into the string to a x/y
+
     #[inline]
-    pub fn index_to_row_col(&self, index: usize) -> (usize, usize) {
-        (index / self.cols, index % self.cols)
+    pub fn get_signed(&self, row: isize, col: isize) -> Option<&T> {
+        self.index(row, col).map(|index| &self.inner[index])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index(row as isize, col as isize)
+            .map(|index| &mut self.inner[index])
+    }
+
+    /// Sets a cell, errors if it is outside of the grid
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> R<()> {
+        match self.get_mut(row, col) {
+            Some(cell) => *cell = value,
+            None => bail!("Invalid index to set r{},c{}", row, col),
+        }
+        Ok(())
+    }
+
+    /// Every cell along with its row/col
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.inner
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (self.index_to_row_col(i), x))
+    }
+
+    /// Row/col of the first cell matching the predicate
+    pub fn position<F>(&self, f: F) -> Option<(usize, usize)>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.inner.iter().position(f).map(|index| self.index_to_row_col(index))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.inner[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.inner[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.cols);
+        self.inner.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn col_mut(&mut self, col: usize) -> impl DoubleEndedIterator<Item = &mut T> {
+        assert!(col < self.cols);
+        self.inner.iter_mut().skip(col).step_by(self.cols)
+    }
+
+    /// Every row from top to bottom
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.inner.chunks(self.cols.max(1))
+    }
+
+    /// Every col from left to right
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(|col| self.col(col))
+    }
+
+    /// Cells starting at row/col and stepping by d_row/d_col until the edge of the grid, the start is included
+    pub fn walk(
+        &self,
+        row: isize,
+        col: isize,
+        d_row: isize,
+        d_col: isize,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        (0..)
+            .map(move |i| (row + i * d_row, col + i * d_col))
+            .map_while(|(r, c)| Some(((r as usize, c as usize), self.get_signed(r, c)?)))
+    }
+
+    /// Diagonals going down and to the right, starting from the bottom left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (1..self.rows)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((0..self.cols).map(|col| (0, col)));
+        starts.map(|(row, col)| self.walk(row as isize, col as isize, 1, 1).map(|(_, x)| x))
+    }
+
+    /// Diagonals going down and to the left, starting from the top left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_col = self.cols.saturating_sub(1);
+        let starts = (0..self.cols)
+            .map(|col| (0, col))
+            .chain((1..self.rows).map(move |row| (row, last_col)));
+        starts.map(|(row, col)| self.walk(row as isize, col as isize, 1, -1).map(|(_, x)| x))
+    }
+
+    /// Swaps rows and cols
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            inner: self.iter_cols().flatten().cloned().collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
     }
+}
 
-    pub fn get_adjacent8(&self, row: usize, col: usize) -> [Option<char>; 8] {
+impl<T> Grid<T>
+where
+    T: Copy,
+{
+    pub fn get_adjacent8(&self, row: usize, col: usize) -> [Option<T>; 8] {
         let r = row as isize;
         let c = col as isize;
         [
-            self.get_char_signed(r + 1, c + 1),
-            self.get_char_signed(r + 1, c),
-            self.get_char_signed(r + 1, c - 1),
-            self.get_char_signed(r - 1, c + 1),
-            self.get_char_signed(r - 1, c),
-            self.get_char_signed(r - 1, c - 1),
-            self.get_char_signed(r, c + 1),
-            self.get_char_signed(r, c - 1),
+            self.get_signed(r + 1, c + 1).copied(),
+            self.get_signed(r + 1, c).copied(),
+            self.get_signed(r + 1, c - 1).copied(),
+            self.get_signed(r - 1, c + 1).copied(),
+            self.get_signed(r - 1, c).copied(),
+            self.get_signed(r - 1, c - 1).copied(),
+            self.get_signed(r, c + 1).copied(),
+            self.get_signed(r, c - 1).copied(),
         ]
     }
-    pub fn get_adjacent4(&self, row: usize, col: usize) -> [Option<char>; 4] {
+    pub fn get_adjacent4(&self, row: usize, col: usize) -> [Option<T>; 4] {
         let r = row as isize;
         let c = col as isize;
         [
-            self.get_char_signed(r + 1, c),
-            self.get_char_signed(r - 1, c),
-            self.get_char_signed(r, c + 1),
-            self.get_char_signed(r, c - 1),
+            self.get_signed(r + 1, c).copied(),
+            self.get_signed(r - 1, c).copied(),
+            self.get_signed(r, c + 1).copied(),
+            self.get_signed(r, c - 1).copied(),
         ]
     }
+}
 
-    /// Sets a char in the string to a new char, requires string to be ASCII
-    pub fn set_char(&mut self, row: usize, col: usize, c: char) -> anyhow::Result<()> {
-        if self.inner.is_ascii() {
-            if let Some(index) = self.index(row as isize, col as isize) {
-                // SAFETY: String is checked to be ascii
-                let vec = unsafe { self.inner.as_mut_vec() };
-                vec[index] = c as u8;
-                Ok(())
-            } else {
-                bail!("Invalid index to set char")
+impl<T> Grid<T> {
+    /// Shrinks a maze to a graph of its junctions, open cells with 3 or more open neighbours, and the `endpoints`.
+    /// Edges are the steps along the corridor between two nodes, dead ends are dropped. The endpoints come first in
+    /// the graph, then the junctions row by row.
+    ///
+    /// A directed graph has an edge each way along a corridor unless a `one_way` cell is in the way, `one_way` gives
+    /// the only direction a cell can be left in, Ex: `>` slopes. An undirected graph has one edge per corridor and
+    /// ignores `one_way`
+    pub fn contract<Ty, F, W>(
+        &self,
+        endpoints: &[Point2<usize>],
+        open: F,
+        one_way: W,
+    ) -> Graph<Point2<usize>, usize, Ty>
+    where
+        Ty: EdgeType,
+        F: Fn(&T) -> bool,
+        W: Fn(&T) -> Option<Dir4>,
+    {
+        let is_open = |point: &Point2<usize>| self.get(point.y, point.x).is_some_and(&open);
+        let neighbors = |point: Point2<usize>| {
+            Dir4::ALL
+                .into_iter()
+                .filter_map(move |dir| Some((dir, point.checked_step(dir)?)))
+                .filter(|(_, next)| is_open(next))
+        };
+        // Cells that can be moved to from the point
+        let moves = |point: Point2<usize>| {
+            let only = if Ty::is_directed() { one_way(&self[point]) } else { None };
+            neighbors(point)
+                .filter(move |(dir, _)| only.is_none_or(|only| only == *dir))
+                .map(|(_, next)| next)
+        };
+
+        let mut graph = Graph::default();
+        let mut nodes = HashMap::new();
+        for &point in endpoints {
+            nodes.entry(point).or_insert_with(|| graph.add_node(point));
+        }
+        for point in (0..self.inner.len()).map(|index| self.index_to_point(index)) {
+            if is_open(&point) && neighbors(point).count() >= 3 {
+                nodes.entry(point).or_insert_with(|| graph.add_node(point));
             }
-        } else {
-            bail!("Grid is made up of non-ascii chars, cannot replace single char")
         }
+
+        let mut edges = vec![];
+        for from in graph.node_indices() {
+            for first in moves(graph[from]) {
+                // Follow the corridor until it reaches a node, there is only ever one way on
+                let (mut previous, mut current, mut steps) = (graph[from], first, 1);
+                let to = loop {
+                    if let Some(&to) = nodes.get(&current) {
+                        break Some(to);
+                    }
+                    let Ok(next) = moves(current).filter(|next| *next != previous).exactly_one() else {
+                        break None;
+                    };
+                    (previous, current, steps) = (current, next, steps + 1);
+                };
+                // Undirected corridors are walked from both ends, only keep one of them
+                if let Some(to) = to.filter(|to| Ty::is_directed() || from < *to) {
+                    edges.push((from, to, steps));
+                }
+            }
+        }
+        for (from, to, steps) in edges {
+            graph.add_edge(from, to, steps);
+        }
+        graph
+    }
+}
+
+impl Grid<char> {
+    /// Gets a char from the grid
+    #[inline]
+    pub fn get_char(&self, row: usize, col: usize) -> Option<char> {
+        self.get(row, col).copied()
+    }
+    /// Gets a char from the grid
+    #[inline]
+    pub fn get_char_signed(&self, row: isize, col: isize) -> Option<char> {
+        self.get_signed(row, col).copied()
+    }
+
+    /// Sets a char in the grid to a new char
+    pub fn set_char(&mut self, row: usize, col: usize, c: char) -> anyhow::Result<()> {
+        self.set(row, col, c)
     }
 
     pub fn find_char(&self, c: char) -> Option<(usize, usize)> {
-        self.inner.find(c).map(|index| self.index_to_row_col(index))
+        self.position(|&x| x == c)
     }
 
     // Assumes all characters are unique
     pub fn char_positions(&self) -> BTreeMap<char, (usize, usize)> {
         let mut map = BTreeMap::new();
-        for (i, x) in self.inner.chars().enumerate() {
-            map.insert(x, self.index_to_row_col(i));
+        for (i, x) in self.inner.iter().enumerate() {
+            map.insert(*x, self.index_to_row_col(i));
         }
         // Number of keys should be the same as number of characters
         assert_eq!(map.keys().len(), self.inner.len());
         map
     }
 }
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Cell at row/col, panics outside of the grid
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "r{},c{} is outside of the grid",
+            row,
+            col
+        );
+        &self.inner[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "r{},c{} is outside of the grid",
+            row,
+            col
+        );
+        &mut self.inner[row * self.cols + col]
+    }
+}
+
+/// Prints each row on its own line with nothing between the cells
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "123\n456";
+
+    #[test]
+    fn char_grid() {
+        let mut grid = Grid::new(SAMPLE);
+        assert_eq!((grid.rows, grid.cols), (2, 3));
+        assert_eq!(grid.get_char(1, 2), Some('6'));
+        assert_eq!(grid.get_char_signed(-1, 0), None);
+        assert_eq!(grid.find_char('5'), Some((1, 1)));
+        assert_eq!(grid.get_adjacent4(0, 0), [Some('4'), None, Some('2'), None]);
+        grid.set_char(0, 0, '#').unwrap();
+        assert!(grid.set_char(2, 0, '#').is_err());
+        assert_eq!(grid.to_string(), "#23\n456");
+        assert!(Grid::try_parse("12\n3", Ok).is_err());
+    }
+
+    #[test]
+    fn typed_grid() {
+        let mut grid = Grid::parse_digits(SAMPLE).unwrap();
+        assert_eq!(grid[(1, 0)], 4);
+        grid[(1, 0)] += 1;
+        assert_eq!(grid.map(|x| x * 2).row(1), [10, 10, 12]);
+        assert_eq!(grid.col(2).rev().copied().collect::<Vec<_>>(), [6, 3]);
+        for x in grid.col_mut(0) {
+            *x = 0;
+        }
+        assert_eq!(grid.iter_rows().collect::<Vec<_>>(), [[0, 2, 3], [0, 5, 6]]);
+        assert_eq!(grid.transpose().to_string(), "00\n25\n36");
+        assert_eq!(
+            grid.diagonals().map(|d| d.copied().collect()).collect::<Vec<Vec<_>>>(),
+            [vec![0], vec![0, 5], vec![2, 6], vec![3]]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|d| d.copied().collect())
+                .collect::<Vec<Vec<_>>>(),
+            [vec![0], vec![2, 0], vec![3, 5], vec![6]]
+        );
+        assert_eq!(
+            grid.walk(1, 2, 0, -1).map(|(p, _)| p).collect::<Vec<_>>(),
+            [(1, 2), (1, 1), (1, 0)]
+        );
+        assert!(Grid::parse_digits("1a").is_err());
+        assert_eq!(Grid::filled(2, 2, '.').to_string(), "..\n..");
+    }
+
+    #[test]
+    fn contract() {
+        use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+
+        // Two ways between the junctions, the longer one has a slope
+        let maze = Grid::new("#.#####\n#.>...#\n#.###.#\n#.....#\n###.###");
+        let endpoints = [Point2::new(1, 0), Point2::new(3, 4)];
+        let slope = |&c: &char| (c == '>').then_some(Dir4::Right);
+        let graph: UnGraph<_, _> = maze.contract(&endpoints, |&c| c != '#', slope);
+        assert_eq!(
+            graph.node_weights().copied().collect_vec(),
+            [endpoints[0], endpoints[1], Point2::new(1, 1), Point2::new(3, 3)]
+        );
+        assert_eq!(graph.edge_weights().copied().sorted().collect_vec(), [1, 1, 4, 8]);
+        let graph: DiGraph<_, _> = maze.contract(&endpoints, |&c| c != '#', slope);
+        assert_eq!(graph.edge_count(), 7);
+        let back = graph.edges_connecting(NodeIndex::new(3), NodeIndex::new(2));
+        assert_eq!(back.map(|edge| *edge.weight()).collect_vec(), [4]);
+    }
+}
@@ -0,0 +1,344 @@
+// Providers for the puzzle inputs, either from files on disk or downloaded from the site
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{AdventOfCodeError, R};
+
+/// Used when `AOC_BASE_URL` is not set
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Something that can supply the puzzle input for a year and day
+pub trait InputProvider {
+    fn fetch(&self, year: u16, day: u8) -> R<String>;
+}
+
+/// Downloads inputs from the advent of code site using the session token
+pub struct HttpProvider {
+    pub(crate) base_url: String,
+    pub(crate) session: Option<String>,
+    pub(crate) client: reqwest::blocking::Client,
+}
+
+impl HttpProvider {
+    pub fn new<S>(session: Option<S>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.map(Into::into),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Session token comes from `AOC_SESSION`, `AOC_BASE_URL` can point to a different server (Ex: a local stub)
+    pub fn from_env() -> Self {
+        let provider = Self::new(env::var("AOC_SESSION").ok());
+        match env::var("AOC_BASE_URL") {
+            Ok(base_url) => provider.with_base_url(base_url),
+            Err(_) => provider,
+        }
+    }
+
+    /// Like `from_env` but uses the profile's saved session token when there is one
+    pub fn for_store(store: &InputStore) -> R<Self> {
+        let provider = Self::from_env();
+        match store.session()? {
+            Some(session) => Ok(provider.with_session(session)),
+            None => Ok(provider),
+        }
+    }
+
+    pub fn with_session<S>(mut self, session: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn with_base_url<S>(mut self, base_url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+impl InputProvider for HttpProvider {
+    fn fetch(&self, year: u16, day: u8) -> R<String> {
+        // Check for the session first so nothing is sent without it
+        let session = self.session.as_ref().ok_or(AdventOfCodeError::MissingSession)?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let res = self
+            .client
+            .get(&url)
+            .header("Cookie", format!("session={}", session))
+            .send()?;
+        let status = res.status().as_u16();
+        let body = res.text()?;
+        if body.contains("Please log in") {
+            // Expired or invalid session tokens get sent to the log in page instead of the input
+            Err(AdventOfCodeError::NotLoggedIn)?
+        } else if status != 200 {
+            Err(AdventOfCodeError::HttpStatus { status, url })?
+        }
+        Ok(body)
+    }
+}
+
+/// Profile used when `AOC_PROFILE` is not set
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Inputs saved outside of the source tree at `<root>/<profile>/<year>/<day>.txt`.
+///
+/// Each profile is a different account, so several accounts' inputs can be kept side by side.
+/// A profile's session token can be saved in `<root>/<profile>/session`.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+    profile: String,
+}
+
+impl InputStore {
+    pub fn new<T, S>(root: T, profile: S) -> Self
+    where
+        T: Into<PathBuf>,
+        S: Into<String>,
+    {
+        Self {
+            root: root.into(),
+            profile: profile.into(),
+        }
+    }
+
+    /// Root comes from `AOC_INPUT_DIR`, defaults to `$XDG_CACHE_HOME/aoc` (or `~/.cache/aoc`).
+    /// Profile comes from `AOC_PROFILE`
+    pub fn from_env() -> Self {
+        let root = env::var_os("AOC_INPUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| default_cache_dir().join("aoc"));
+        let profile = env::var("AOC_PROFILE").unwrap_or_else(|_| DEFAULT_PROFILE.to_string());
+        Self::new(root, profile)
+    }
+
+    pub fn with_profile<S>(mut self, profile: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.profile = profile.into();
+        self
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn is_default_profile(&self) -> bool {
+        self.profile == DEFAULT_PROFILE
+    }
+
+    /// Where the input for the day is saved
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(&self.profile)
+            .join(year.to_string())
+            .join(format!("{:02}.txt", day))
+    }
+
+    /// Where the answers submitted for the day are logged
+    pub fn submissions_path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(&self.profile)
+            .join(year.to_string())
+            .join(format!("{:02}.submissions.toml", day))
+    }
+
+    /// Session token saved for the profile, if there is one
+    pub fn session(&self) -> R<Option<String>> {
+        let path = self.root.join(&self.profile).join("session");
+        if path.is_file() {
+            Ok(Some(fs::read_to_string(path)?.trim().to_string()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Names of every profile in the store
+    pub fn profiles(&self) -> R<Vec<String>> {
+        if !self.root.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut profiles = vec![];
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                profiles.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        profiles.sort();
+        Ok(profiles)
+    }
+}
+
+fn default_cache_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".cache")
+    } else if let Some(dir) = env::var_os("LOCALAPPDATA") {
+        PathBuf::from(dir)
+    } else {
+        env::temp_dir()
+    }
+}
+
+/// Reads inputs saved in the store, anything missing is fetched from the backend and saved for next time
+pub struct FileCache<P> {
+    store: InputStore,
+    backend: P,
+}
+
+impl<P> FileCache<P>
+where
+    P: InputProvider,
+{
+    pub fn new(store: InputStore, backend: P) -> Self {
+        Self { store, backend }
+    }
+
+    /// Makes sure the input is saved, returns where it is
+    pub fn save(&self, year: u16, day: u8) -> R<PathBuf> {
+        let path = self.store.path(year, day);
+        if !path.is_file() {
+            // Only save the input once the backend succeeded, so errors are never cached
+            let input = self.backend.fetch(year, day)?;
+            fs::create_dir_all(path.parent().expect("Store path always has a parent"))?;
+            fs::write(&path, input)?;
+        }
+        Ok(path)
+    }
+}
+
+impl<P> InputProvider for FileCache<P>
+where
+    P: InputProvider,
+{
+    fn fetch(&self, year: u16, day: u8) -> R<String> {
+        Ok(fs::read_to_string(self.save(year, day)?)?)
+    }
+}
+
+/// Finds the input file for a crate's `Input.txt` path, downloading it into the store if needed.
+///
+/// `Input.txt` files saved next to the crate are still used for the default profile
+pub fn resolve_input_path(input: &Path) -> R<PathBuf> {
+    let store = InputStore::from_env();
+    if input.is_file() && (store.is_default_profile() || input.file_name() != Some("Input.txt".as_ref())) {
+        return Ok(input.to_path_buf());
+    }
+    let (year, day) = parse_input_path(input)?;
+    let backend = HttpProvider::for_store(&store)?;
+    FileCache::new(store, backend).save(year, day)
+}
+
+/// Gets the year and day from an input path in the format `<year>/day<day>/Input.txt`
+pub fn parse_input_path(input: &Path) -> R<(u16, u8)> {
+    if input.file_name().and_then(|x| x.to_str()) != Some("Input.txt") {
+        // We can only download Input.txt, there is no API for sample
+        Err(AdventOfCodeError::DownloadError)?
+    }
+    let day_dir = input.parent().ok_or(AdventOfCodeError::DayError)?;
+    let year_dir = day_dir.parent().ok_or(AdventOfCodeError::YearError)?;
+    let day = day_dir
+        .file_name()
+        .and_then(|x| x.to_str())
+        .and_then(|x| x.get(x.len().checked_sub(2)?..))
+        .and_then(|x| x.parse().ok())
+        .ok_or(AdventOfCodeError::DayError)?;
+    let year = year_dir
+        .file_name()
+        .and_then(|x| x.to_str())
+        .and_then(|x| x.get(x.len().checked_sub(4)?..))
+        .and_then(|x| x.parse().ok())
+        .ok_or(AdventOfCodeError::YearError)?;
+    Ok((year, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::stub_server;
+
+    #[test]
+    fn http_input() {
+        let (base_url, request) = stub_server("200 OK", "1\n2\n3\n");
+        let provider = HttpProvider::new(Some("abc")).with_base_url(base_url);
+        assert_eq!(provider.fetch(2023, 7).unwrap(), "1\n2\n3\n");
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/7/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn http_errors() {
+        let (base_url, _) = stub_server("404 Not Found", "Please don't repeatedly request this endpoint");
+        let provider = HttpProvider::new(Some("abc")).with_base_url(base_url);
+        let err = provider.fetch(2023, 25).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref(),
+            Some(AdventOfCodeError::HttpStatus { status: 404, .. })
+        ));
+
+        let (base_url, _) = stub_server(
+            "200 OK",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        let provider = HttpProvider::new(Some("expired")).with_base_url(base_url);
+        let err = provider.fetch(2023, 1).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(AdventOfCodeError::NotLoggedIn)));
+
+        let provider = HttpProvider::new(None::<String>).with_base_url("http://127.0.0.1:1");
+        let err = provider.fetch(2023, 1).unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(AdventOfCodeError::MissingSession)));
+    }
+
+    #[test]
+    fn file_cache() {
+        let root = env::temp_dir().join(format!("aoc-file-cache-{}", std::process::id()));
+        let store = InputStore::new(&root, "alice");
+
+        // Failed downloads are not saved
+        let (base_url, _) = stub_server("500 Internal Server Error", "oops");
+        let cache = FileCache::new(store.clone(), HttpProvider::new(Some("abc")).with_base_url(base_url));
+        assert!(cache.fetch(2022, 1).is_err());
+        assert!(!store.path(2022, 1).exists());
+
+        let (base_url, _) = stub_server("200 OK", "42\n");
+        let cache = FileCache::new(store.clone(), HttpProvider::new(Some("abc")).with_base_url(base_url));
+        assert_eq!(cache.fetch(2022, 1).unwrap(), "42\n");
+        assert_eq!(store.path(2022, 1), root.join("alice/2022/01.txt"));
+        // Second fetch comes from the saved file, the stub server only answers once
+        assert_eq!(cache.fetch(2022, 1).unwrap(), "42\n");
+
+        fs::write(root.join("alice/session"), "token\n").unwrap();
+        fs::create_dir_all(root.join("bob")).unwrap();
+        assert_eq!(store.session().unwrap().as_deref(), Some("token"));
+        assert_eq!(store.clone().with_profile("bob").session().unwrap(), None);
+        assert_eq!(store.profiles().unwrap(), ["alice", "bob"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn input_path() {
+        assert_eq!(
+            parse_input_path(Path::new("/aoc/2023/day17/Input.txt")).unwrap(),
+            (2023, 17)
+        );
+        assert!(parse_input_path(Path::new("/aoc/2023/day17/Sample.txt")).is_err());
+    }
+}
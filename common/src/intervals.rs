@@ -0,0 +1,287 @@
+// Sets of numbers kept as sorted ranges, for puzzles with ranges far too big to hold each number
+
+use std::ops::{Range, RangeInclusive};
+
+use num::PrimInt;
+
+/// A range of numbers given as either `a..b` or `a..=b`
+pub trait Interval<T> {
+    /// The same numbers as a half open range
+    fn into_range(self) -> Range<T>;
+}
+
+impl<T> Interval<T> for Range<T>
+where
+    T: PrimInt,
+{
+    fn into_range(self) -> Range<T> {
+        self
+    }
+}
+
+impl<T> Interval<T> for RangeInclusive<T>
+where
+    T: PrimInt,
+{
+    /// Panics when the end is `T::max_value()` as it can't be represented as a half open range
+    fn into_range(self) -> Range<T> {
+        let (start, end) = self.into_inner();
+        start..end + T::one()
+    }
+}
+
+/// Set of numbers stored as sorted ranges that don't overlap or touch, ranges are merged as they are inserted
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges in order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many numbers are in the set
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::zero(), |sum, r| sum + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    /// Binary search for the range holding the number
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(index).is_some_and(|r| r.start <= value)
+    }
+
+    /// Adds the range merging it with any it overlaps or touches
+    pub fn insert<I>(&mut self, range: I)
+    where
+        I: Interval<T>,
+    {
+        let Range { mut start, mut end } = range.into_range();
+        if start >= end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < start);
+        let last = self.ranges.partition_point(|r| r.start <= end);
+        if first < last {
+            start = start.min(self.ranges[first].start);
+            end = end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [start..end]);
+    }
+
+    /// Takes the range out of the set, splitting any range it is in the middle of
+    pub fn remove<I>(&mut self, range: I)
+    where
+        I: Interval<T>,
+    {
+        let Range { start, end } = range.into_range();
+        if start >= end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= start);
+        let last = self.ranges.partition_point(|r| r.start < end);
+        if first >= last {
+            return;
+        }
+        let before = self.ranges[first].start..start;
+        let after = end..self.ranges[last - 1].end;
+        let kept = [before, after].into_iter().filter(|r| r.start < r.end);
+        self.ranges.splice(first..last, kept.collect::<Vec<_>>());
+    }
+
+    /// Numbers in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    /// Numbers in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (0, 0);
+        let mut ranges = vec![];
+        while let (Some(x), Some(y)) = (self.ranges.get(a), other.ranges.get(b)) {
+            let (start, end) = (x.start.max(y.start), x.end.min(y.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            // Move past whichever range ends first
+            if x.end < y.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Numbers in this set but not the other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in &other.ranges {
+            difference.remove(range.clone());
+        }
+        difference
+    }
+}
+
+impl<T, I> FromIterator<I> for IntervalSet<T>
+where
+    T: PrimInt,
+    I: Interval<T>,
+{
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// Moves numbers in source ranges by an offset, numbers outside of every source range stay the same.
+///
+/// Ex: the map `10..20 -> 50` turns 12 into 52 and 25 into 25
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// Source ranges sorted by start, each with where the start moves to
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the source range to start at `destination`, source ranges must not overlap
+    pub fn insert<I>(&mut self, source: I, destination: T)
+    where
+        I: Interval<T>,
+    {
+        let source = source.into_range();
+        let index = self.entries.partition_point(|(r, _)| r.start < source.start);
+        self.entries.insert(index, (source, destination));
+    }
+
+    /// Where a single number moves to
+    pub fn get(&self, value: T) -> T {
+        let index = self.entries.partition_point(|(r, _)| r.end <= value);
+        match self.entries.get(index) {
+            Some((source, destination)) if source.start <= value => value - source.start + *destination,
+            _ => value,
+        }
+    }
+
+    /// Where every number in the set moves to, ranges are split wherever they cross a source range edge
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut mapped = IntervalSet::new();
+        for range in set.ranges() {
+            let mut start = range.start;
+            let first = self.entries.partition_point(|(r, _)| r.end <= start);
+            for (source, destination) in &self.entries[first..] {
+                if source.start >= range.end {
+                    break;
+                }
+                // Unmapped gap before this source range
+                if start < source.start {
+                    mapped.insert(start..source.start);
+                    start = source.start;
+                }
+                let end = range.end.min(source.end);
+                mapped.insert(start - source.start + *destination..end - source.start + *destination);
+                start = end;
+            }
+            mapped.insert(start..range.end);
+        }
+        mapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_set() {
+        let mut set: IntervalSet<u32> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.ranges(), [3..6, 10..21]);
+        assert_eq!(set.len(), 14);
+        assert!(set.contains(5) && set.contains(10) && set.contains(20));
+        assert!(!set.contains(6) && !set.contains(2) && !set.contains(21));
+        // Touching ranges merge
+        set.insert(6..10);
+        assert_eq!((set.ranges().len(), set.min(), set.max()), (1, Some(3), Some(20)));
+        set.remove(8..=9);
+        set.remove(0..4);
+        assert_eq!(set.ranges(), [4..8, 10..21]);
+        assert_eq!((set.min(), set.max()), (Some(4), Some(20)));
+        set.remove(5..15);
+        assert_eq!(set.ranges(), [4..5, 15..21]);
+        set.insert(0..0);
+        assert_eq!(set.ranges().len(), 2);
+
+        let a: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<i64> = [-5..2, 5..25].into_iter().collect();
+        let union = a.union(&b);
+        assert_eq!(
+            (union.ranges().len(), union.min(), union.max()),
+            (1, Some(-5), Some(29))
+        );
+        assert_eq!(a.intersection(&b).ranges(), [0..2, 5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), [2..5, 25..30]);
+        assert_eq!(b.difference(&a).ranges(), [-5..0, 10..20]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn range_map() {
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(
+            [0, 49, 50, 97, 98, 99, 100].map(|x| map.get(x)),
+            [0, 49, 52, 99, 50, 51, 100]
+        );
+        let seeds: IntervalSet<u64> = [40..=55, 95..=105].into_iter().collect();
+        // 40..50 stays, 50..56 -> 52..58, 95..98 -> 97..100, 98..100 -> 50..52, 100..106 stays
+        assert_eq!(map.map_set(&seeds).ranges(), [40..58, 97..106]);
+    }
+}
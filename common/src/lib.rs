@@ -1,5 +1,4 @@
 use std::{
-    env,
     fs::{self, File},
     io::{self, BufRead, BufReader, Lines},
     path::Path,
@@ -13,8 +12,8 @@ pub fn read_input_as_string<P>(input: P) -> String
 where
     P: AsRef<Path>,
 {
-    match download_file_if_doesnt_exist(input.as_ref()) {
-        Ok(_) => fs::read_to_string(input).unwrap(),
+    match resolve_input_path(input.as_ref()) {
+        Ok(path) => fs::read_to_string(path).unwrap(),
         Err(err) => panic!("{}", err),
     }
 }
@@ -23,9 +22,9 @@ pub fn read_input_as_lines<P>(input: P) -> io::Result<Lines<BufReader<File>>>
 where
     P: AsRef<Path>,
 {
-    match download_file_if_doesnt_exist(input.as_ref()) {
-        Ok(_) => {
-            let file = File::open(input)?;
+    match resolve_input_path(input.as_ref()) {
+        Ok(path) => {
+            let file = File::open(path)?;
             let reader = BufReader::new(file);
             Ok(reader.lines())
         }
@@ -43,6 +42,14 @@ pub enum AdventOfCodeError {
     UnimplementedError,
     #[error("Cannot Downlaod files other than 'Input.txt'")]
     DownloadError,
+    #[error("AOC_SESSION env var not found, set it to the session token to download inputs")]
+    MissingSession,
+    #[error("Session token was not accepted, the site asked to log in")]
+    NotLoggedIn,
+    #[error("Request to {url} failed with status {status}")]
+    HttpStatus { status: u16, url: String },
+    #[error("Answer was not submitted, {0}")]
+    NotSubmitted(String),
     #[error("Custom Error: {0}")]
     Custom(String),
 }
@@ -52,44 +59,9 @@ impl AdventOfCodeError {
     }
 }
 
-fn download_file_if_doesnt_exist(input: &Path) -> anyhow::Result<()> {
-    if input.is_file() {
-        // file already exists, no need to do anthing
-        Ok(())
-    } else if input.file_name().expect("Input should be a file") == "Input.txt" {
-        // We can only download Input.txt, there is no API for sample
-        // Get the folder name to determine year and day
-        let mut it = input.iter().rev();
-        let _ = it.next(); // This is the file name it can be ignored
-        let day = it
-            .next()
-            .ok_or(AdventOfCodeError::DayError)?
-            .to_str()
-            .ok_or(AdventOfCodeError::DayError)?;
-        let year = it
-            .next()
-            .ok_or(AdventOfCodeError::YearError)?
-            .to_str()
-            .ok_or(AdventOfCodeError::YearError)?;
-        let day = &day[day.len() - 2..].parse::<usize>()?;
-        let year = &year[year.len() - 4..];
-        // Session token to come from envvar
-        let session = env::var("AOC_SESSION").expect("AOC_SESSION env var not found");
-        let client = reqwest::blocking::Client::new();
-        let res = client
-            .get(format!("https://adventofcode.com/{}/day/{}/input", year, day))
-            .header("Cookie", format!("session={}", session))
-            .send()?;
-        fs::write(input, res.text()?)?;
-        Ok(())
-    } else {
-        Err(AdventOfCodeError::DownloadError)?
-    }
-}
-
 pub struct Timer {
     message: String,
-    start_time: time::SystemTime,
+    start_time: time::Instant,
 }
 
 impl Timer {
@@ -99,18 +71,19 @@ impl Timer {
     {
         Timer {
             message: message.into(),
-            start_time: time::SystemTime::now(),
+            start_time: time::Instant::now(),
         }
     }
 }
 
 impl Drop for Timer {
     fn drop(&mut self) {
-        println!("Time of {}, {:?}", self.message, self.start_time.elapsed().unwrap())
+        println!("Time of {}, {:?}", self.message, self.start_time.elapsed())
     }
 }
 
-/// Macro for some boiler plate code to read in the Input.txt file for the given package
+/// Macro for some boiler plate code to read in the Input.txt file for the given package, the input comes from the
+/// `InputStore` unless it is saved next to the package
 #[macro_export]
 macro_rules! read_input_file_for_project {
     () => {
@@ -124,14 +97,53 @@ macro_rules! read_input_file_for_project_as_string {
         common::read_input_as_string(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("Input.txt"))
     };
 }
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+pub mod difftest;
+pub mod dot;
+pub mod geom;
 pub mod grid;
+pub mod input;
+pub mod intervals;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod polygon;
+pub mod samples;
+pub mod search;
+pub mod solution;
+pub mod submit;
+#[cfg(test)]
+mod test_util;
+pub mod viz;
 
+pub use answers::*;
 pub use anyhow;
+pub use bench::*;
+pub use cycle::*;
+pub use difftest::*;
+pub use dot::*;
+pub use geom::*;
 pub use grid::*;
+pub use input::*;
+pub use intervals::*;
 pub use itertools::Itertools;
+pub use math::*;
+pub use memo::*;
 pub use num;
+pub use ocr::*;
+pub use parse::*;
 pub use petgraph;
 pub use petgraph::prelude::*;
+pub use polygon::*;
+pub use rand;
 pub use rayon::prelude::*;
 pub use regex;
+pub use samples::*;
+pub use search::*;
+pub use solution::*;
+pub use submit::*;
+pub use viz::*;
 pub use winnow;
@@ -0,0 +1,150 @@
+// Number theory for puzzles that loop on different periods or ask for answers modulo something
+
+use num::{CheckedMul, Integer, Signed};
+
+/// Greatest common divisor along with `x` and `y` where `a * x + b * y = gcd`
+pub fn extended_gcd<T>(a: T, b: T) -> (T, T, T)
+where
+    T: Integer + Signed + Copy,
+{
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` where `a * x` is 1 modulo `modulus`, None when `a` and `modulus` share a factor or `modulus` isn't positive
+pub fn mod_inverse<T>(a: T, modulus: T) -> Option<T>
+where
+    T: Integer + Signed + Copy,
+{
+    if !modulus.is_positive() {
+        return None;
+    }
+    let (gcd, x, _) = extended_gcd(a, modulus);
+    gcd.is_one().then(|| x.mod_floor(&modulus))
+}
+
+/// `base` to the power of `exp` modulo `modulus` by repeated squaring, None when `modulus` is 0
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    match modulus {
+        0 => return None,
+        1 => return Some(0),
+        _ => (),
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// Smallest number every number divides into, None if it overflows. Ex: when a few ghosts loop at different periods
+///
+/// 0 when any number is 0, like `Integer::lcm`
+pub fn lcm_many<T>(numbers: &[T]) -> Option<T>
+where
+    T: Integer + CheckedMul + Copy,
+{
+    numbers.iter().try_fold(T::one(), |lcm, &n| {
+        if lcm.is_zero() || n.is_zero() {
+            Some(T::zero())
+        } else {
+            (lcm / lcm.gcd(&n)).checked_mul(&n)
+        }
+    })
+}
+
+/// Largest number that divides every number, 0 for no numbers
+pub fn gcd_many<T>(numbers: &[T]) -> T
+where
+    T: Integer + Copy,
+{
+    numbers.iter().fold(T::zero(), |gcd, n| gcd.gcd(n))
+}
+
+/// Chinese remainder theorem, the smallest `x >= 0` where `x % modulus == residue` for every `(residue, modulus)`.
+///
+/// Returns `x` with the lcm of the moduli, every answer is `x` plus a multiple of it. The moduli don't have to be
+/// coprime, None when the congruences contradict each other, a modulus isn't positive or the lcm overflows
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, lcm): (i128, i128), &(residue, modulus)| {
+            if modulus <= 0 {
+                return None;
+            }
+            let (gcd, p, _) = extended_gcd(lcm, modulus);
+            let difference = residue - x;
+            if difference % gcd != 0 {
+                return None;
+            }
+            let step = modulus / gcd;
+            // Number of lcm sized jumps to get from x to the residue
+            let jumps = (difference / gcd).mod_floor(&step).checked_mul(p.mod_floor(&step))? % step;
+            let new_lcm = lcm.checked_mul(step)?;
+            Some((x.checked_add(lcm.checked_mul(jumps)?)?.mod_floor(&new_lcm), new_lcm))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_inverse() {
+        let (gcd, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(gcd, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4i32, 6).0, 2);
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(4i64, 12), None);
+        assert_eq!(mod_inverse(1i64, 0), None);
+        assert_eq!(mod_inverse(3i64, -11), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), Some(254_368_884));
+        assert_eq!(mod_pow(5, 0, 1), Some(0));
+        assert_eq!(mod_pow(5, 3, 0), None);
+    }
+
+    #[test]
+    fn many() {
+        assert_eq!(lcm_many(&[2u64, 3, 4]), Some(12));
+        assert_eq!(lcm_many::<u64>(&[]), Some(1));
+        assert_eq!(lcm_many(&[u64::MAX, 2]), None);
+        assert_eq!(lcm_many(&[u128::from(u64::MAX), 2]), Some(u128::from(u64::MAX) * 2));
+        assert_eq!(lcm_many(&[4i128, 6]), Some(12));
+        assert_eq!(lcm_many(&[0u64, 0]), Some(0));
+        assert_eq!(lcm_many(&[3u64, 0, u64::MAX]), Some(0));
+        assert_eq!(gcd_many(&[12u32, 18, 30]), 6);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli that share a factor
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+        assert_eq!(crt(&[(1, -5)]), None);
+    }
+}
@@ -0,0 +1,236 @@
+// Caches for recursive functions so days don't pass a `&mut HashMap` through every call by hand
+
+use std::{collections::HashMap, fmt, hash::Hash, mem, sync::Mutex};
+
+/// Stats of the named memos dropped since they were last taken
+static DROPPED: Mutex<Vec<(String, MemoStats)>> = Mutex::new(Vec::new());
+
+/// How often a memo reused a saved answer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Answers saved when the stats were read
+    pub cached: usize,
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hits, {} misses, {} cached", self.hits, self.misses, self.cached)
+    }
+}
+
+/// Takes the stats of the named memos dropped since the last call, oldest first. `common::run` prints them after
+/// the time of the part the memos were made in, Ex: `Memo of blinks, 12 hits, 5 misses, 5 cached`
+pub fn take_memo_stats() -> Vec<(String, MemoStats)> {
+    mem::take(&mut *DROPPED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/// Answers of a recursive function by its arguments, along with how often a saved answer was reused
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    /// Saves the stats for `take_memo_stats` when dropped, unnamed memos save nothing
+    name: Option<String>,
+    limit: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            name: None,
+            limit: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Saves its stats when dropped so `common::run` can print them after the part's time
+    pub fn named<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut memo = Self::new();
+        memo.name = Some(name.into());
+        memo
+    }
+
+    /// Forgets everything once `limit` answers are saved so memory stays bounded, Ex: for keys that rarely repeat
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The saved answer for `key`, or works it out with `f` and saves it. `f` gets the memo back to recurse with
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = f(self);
+        if self.limit.is_some_and(|limit| self.cache.len() >= limit) {
+            self.cache.clear();
+        }
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Memoises `f` without it needing a memo parameter, it recurses by calling the function it is given,
+    /// Ex: `memo.call(n, &|fib, n| if n < 2 { n } else { fib(n - 1) + fib(n - 2) })`
+    pub fn call<F>(&mut self, key: K, f: &F) -> V
+    where
+        K: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        self.get_or_insert_with(key.clone(), |memo| f(&mut |next| memo.call(next, f), key))
+    }
+
+    /// Forgets the saved answers but keeps counting hits and misses, Ex: between lines of input that share nothing
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cache.iter()
+    }
+}
+
+impl<K, V> Memo<K, V> {
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            cached: self.len(),
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K, V> Default for Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Display for Memo<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.stats())
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if let Some(name) = self.name.take() {
+            let stats = self.stats();
+            DROPPED
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push((name, stats));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+    }
+
+    #[test]
+    fn recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        // Each number above 1 is worked out once and reused once by the number 2 above it
+        assert_eq!((memo.misses(), memo.hits(), memo.len()), (89, 87, 89));
+        assert_eq!(memo.to_string(), "87 hits, 89 misses, 89 cached");
+
+        let mut memo = Memo::new();
+        let fib = |fib: &mut dyn FnMut(u64) -> u64, n| if n < 2 { n } else { fib(n - 1) + fib(n - 2) };
+        assert_eq!(memo.call(90, &fib), 2880067194370816120);
+        assert_eq!(memo.get(&10), Some(&55));
+    }
+
+    #[test]
+    fn named_stats() {
+        {
+            let mut memo = Memo::named("memo test fib");
+            fib(10, &mut memo);
+            assert_eq!(
+                memo.stats(),
+                MemoStats {
+                    hits: 7,
+                    misses: 9,
+                    cached: 9
+                }
+            );
+        }
+        // Other tests may drop named memos at the same time
+        let stats = take_memo_stats()
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("memo test"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stats,
+            [(
+                "memo test fib".to_string(),
+                MemoStats {
+                    hits: 7,
+                    misses: 9,
+                    cached: 9
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn limit_and_clear() {
+        let mut memo = Memo::new().with_limit(10);
+        fib(50, &mut memo);
+        assert!(memo.len() <= 10);
+        assert_eq!(fib(50, &mut memo), 12586269025);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        let misses = memo.misses();
+        fib(5, &mut memo);
+        assert_eq!(memo.misses(), misses + 4);
+    }
+}
@@ -0,0 +1,200 @@
+// Reads answers drawn as block letters, Ex: a screen of # and . that spells out 8 capital letters
+
+use itertools::Itertools;
+
+use crate::{AdventOfCodeError, Grid, R};
+
+/// Letters 6 pixels tall, most are 4 wide with a blank column between them
+const SMALL_LETTERS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters 10 pixels tall and 6 wide
+const LARGE_LETTERS: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads the letters from pixels stored row by row, `true` is a lit pixel.
+///
+/// Blank rows around the letters are ignored and letters are split on blank columns. Errors with a drawing of the
+/// first glyph that isn't a known letter
+pub fn read_letters(pixels: &[bool], cols: usize) -> R<String> {
+    let rows = pixels.chunks(cols).collect::<Vec<_>>();
+    let lit_rows = rows.iter().positions(|row| row.contains(&true)).collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else {
+        Err(AdventOfCodeError::new("No letters, every pixel is blank"))?
+    };
+    let rows = &rows[top..=bottom];
+    let letters = match rows.len() {
+        6 => &SMALL_LETTERS[..],
+        10 => &LARGE_LETTERS[..],
+        height => Err(AdventOfCodeError::new(format!(
+            "Letters are {} pixels tall, only 6 and 10 are known",
+            height
+        )))?,
+    };
+    let blank_col = |col: usize| rows.iter().all(|row| !row[col]);
+    let mut answer = String::new();
+    let mut col = 0;
+    while col < cols {
+        if blank_col(col) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < cols && !blank_col(col) {
+            col += 1;
+        }
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                row[start..col]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n");
+        match letters.iter().find(|(_, drawing)| *drawing == glyph) {
+            Some((letter, _)) => answer.push(*letter),
+            None => Err(AdventOfCodeError::new(format!(
+                "Unknown letter at column {}:\n{}",
+                start, glyph
+            )))?,
+        }
+    }
+    Ok(answer)
+}
+
+impl Grid<bool> {
+    pub fn read_letters(&self) -> R<String> {
+        read_letters(&self.inner, self.cols)
+    }
+}
+
+impl Grid<char> {
+    /// Reads the letters drawn with `#`, any other character is blank
+    pub fn read_letters(&self) -> R<String> {
+        read_letters(&self.map(|&c| c == '#').inner, self.cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_letters() {
+        let screen = "\
+###..####.#....###..###..####..##..#..#.
+#..#....#.#....#..#.#..#....#.#..#.#..#.
+#..#...#..#....#..#.###....#..#..#.#..#.
+###...#...#....###..#..#..#...####.#..#.
+#.#..#....#....#.#..#..#.#....#..#.#..#.
+#..#.####.####.#..#.###..####.#..#..##..";
+        assert_eq!(Grid::new(screen).read_letters().unwrap(), "RZLRBZAU");
+
+        // Blank rows and wide gaps are fine
+        let padded = screen.lines().map(|line| format!("...{}", line)).join("\n");
+        let padded = format!("{}\n{}\n{}", ".".repeat(43), padded, ".".repeat(43));
+        assert_eq!(Grid::new(&padded).read_letters().unwrap(), "RZLRBZAU");
+    }
+
+    #[test]
+    fn large_letters() {
+        let pixels = LARGE_LETTERS[13].1.replace('\n', "");
+        let pixels = pixels.chars().map(|c| c == '#').collect::<Vec<_>>();
+        assert_eq!(read_letters(&pixels, 6).unwrap(), "X");
+    }
+
+    #[test]
+    fn one_glyph_per_letter() {
+        for letters in [&SMALL_LETTERS[..], &LARGE_LETTERS[..]] {
+            assert!(letters.iter().map(|(letter, _)| letter).all_unique());
+            assert!(letters.iter().map(|(_, drawing)| drawing).all_unique());
+        }
+    }
+
+    #[test]
+    fn unknown_letter() {
+        let error = Grid::new("#.#\n.#.\n#.#\n.#.\n#.#\n.#.").read_letters().unwrap_err();
+        assert!(error.to_string().contains("#.#\n.#.\n#.#"));
+        assert!(Grid::new("...\n...").read_letters().is_err());
+        assert!(Grid::new("#\n#\n#").read_letters().is_err());
+    }
+}
@@ -0,0 +1,238 @@
+// Winnow parsers for the shapes puzzle inputs come in, Ex: `x,y` points, `key: value` lines and blank line sections
+
+use std::str::FromStr;
+
+use winnow::{
+    ascii::{digit1, line_ending, space0, space1},
+    combinator::{opt, separated, separated_pair},
+    error::{ContextError, ParseError},
+    token::{one_of, take_till},
+    PResult, Parser,
+};
+
+use crate::{AdventOfCodeError, Grid, Point2, Point3, R};
+
+/// Whole number with an optional `-` or `+` sign, Ex: `-12`. Works for any integer type, a `-` fails for unsigned
+pub fn int<T>(input: &mut &str) -> PResult<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    (opt(one_of(['-', '+'])), digit1)
+        .recognize()
+        .try_map(str::parse)
+        .parse_next(input)
+}
+
+/// `,` with any spaces around it
+fn comma(input: &mut &str) -> PResult<()> {
+    (space0, ',', space0).void().parse_next(input)
+}
+
+/// One or more items separated by commas, Ex: `1,2, 3`
+pub fn comma_list<'a, O, P>(item: P) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    separated(1.., item, comma)
+}
+
+/// One or more items separated by spaces, Ex: `41 48  83`
+pub fn space_list<'a, O, P>(item: P) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    separated(1.., item, space1)
+}
+
+/// One or more items each on their own line, the last newline is left for the caller
+pub fn line_list<'a, O, P>(item: P) -> impl Parser<&'a str, Vec<O>, ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    separated(1.., item, line_ending)
+}
+
+/// `x,y` as a point, spaces around the comma are fine
+pub fn point2<T>(input: &mut &str) -> PResult<Point2<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    separated_pair(int, comma, int)
+        .map(|(x, y)| Point2::new(x, y))
+        .parse_next(input)
+}
+
+/// `x,y,z` as a point, spaces around the commas are fine
+pub fn point3<T>(input: &mut &str) -> PResult<Point3<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    (int, comma, int, comma, int)
+        .map(|(x, _, y, _, z)| Point3::new(x, y, z))
+        .parse_next(input)
+}
+
+/// Two items separated by a comma for values that aren't whole numbers, Ex: `1.5, -2` with `float`
+pub fn pair<'a, O, P>(mut item: P) -> impl Parser<&'a str, (O, O), ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    move |input: &mut &'a str| {
+        let first = item.parse_next(input)?;
+        comma.parse_next(input)?;
+        let second = item.parse_next(input)?;
+        Ok((first, second))
+    }
+}
+
+/// Three items separated by commas, Ex: `19, 13, 30`
+pub fn triple<'a, O, P>(mut item: P) -> impl Parser<&'a str, (O, O, O), ContextError>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    move |input: &mut &'a str| {
+        let (first, second) = pair(item.by_ref()).parse_next(input)?;
+        comma.parse_next(input)?;
+        let third = item.parse_next(input)?;
+        Ok((first, second, third))
+    }
+}
+
+/// `key: value` with any spaces after the colon, Ex: `Register A: 729` with `"Register A"` as the key
+pub fn key_value<'a, K, V, PK, PV>(key: PK, value: PV) -> impl Parser<&'a str, (K, V), ContextError>
+where
+    PK: Parser<&'a str, K, ContextError>,
+    PV: Parser<&'a str, V, ContextError>,
+{
+    separated_pair(key, (':', space0), value)
+}
+
+/// Lines up to the next blank line as a grid of chars, the last newline is left for the caller
+pub fn grid(input: &mut &str) -> PResult<Grid> {
+    line_list(take_till(1.., ['\r', '\n']))
+        .recognize()
+        .map(Grid::new)
+        .parse_next(input)
+}
+
+/// Splits the input on blank lines, Ex: the rules and the updates of a two part input
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        match (start, line.trim_end_matches(['\r', '\n']).is_empty()) {
+            (None, false) => start = Some(offset),
+            (Some(section_start), true) => {
+                sections.push(input[section_start..offset].trim_end_matches(['\r', '\n']));
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some(section_start) = start {
+        sections.push(input[section_start..].trim_end_matches(['\r', '\n']));
+    }
+    sections
+}
+
+/// Runs the parser over the whole input, the error points at the line and column it failed on
+pub fn parse_all<'a, O, P>(mut parser: P, input: &'a str) -> R<O>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    parser.parse(input).map_err(|e| parse_error(input, &e, 1).into())
+}
+
+/// Runs the parser over each line on its own, Ex: one instruction per line
+pub fn parse_lines<'a, O, P>(mut parser: P, input: &'a str) -> R<Vec<O>>
+where
+    P: Parser<&'a str, O, ContextError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(row, line)| parser.parse(line).map_err(|e| parse_error(line, &e, row + 1).into()))
+        .collect()
+}
+
+/// Error with the line and column of the offset along with the line and a `^` under where it failed, `first_line` is
+/// the line number the input starts on
+fn parse_error(input: &str, error: &ParseError<&str, ContextError>, first_line: usize) -> AdventOfCodeError {
+    let offset = error.offset();
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[offset..].find(['\r', '\n']).map_or(input.len(), |i| offset + i);
+    let row = input[..offset].matches('\n').count() + first_line;
+    let col = input[line_start..offset].chars().count() + 1;
+    let reason = error.inner().to_string();
+    let reason = if reason.is_empty() {
+        "unexpected input".to_string()
+    } else {
+        reason
+    };
+    AdventOfCodeError::new(format!(
+        "Parse error at line {}, column {}: {}\n{}\n{}^",
+        row,
+        col,
+        reason,
+        &input[line_start..line_end],
+        " ".repeat(col - 1)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use winnow::ascii::{alpha1, float};
+
+    use super::*;
+
+    #[test]
+    fn numbers_and_lists() {
+        assert_eq!(parse_all(int::<i64>, "-12").unwrap(), -12);
+        assert_eq!(parse_all(int::<u8>, "+7").unwrap(), 7);
+        assert!(parse_all(int::<u32>, "-7").is_err());
+        assert_eq!(parse_all(comma_list(int::<i32>), "1,-2, 3").unwrap(), [1, -2, 3]);
+        assert_eq!(parse_all(space_list(int::<u32>), "41 48  83").unwrap(), [41, 48, 83]);
+        assert_eq!(parse_all(line_list(alpha1), "ab\ncd").unwrap(), ["ab", "cd"]);
+        assert_eq!(parse_all(point2::<usize>, "0,9").unwrap(), Point2::new(0, 9));
+        assert_eq!(parse_all(point3::<i64>, "1,-1, 8").unwrap(), Point3::new(1, -1, 8));
+        assert_eq!(
+            parse_all(triple(float::<_, f64, _>), "19, 13,  30.5").unwrap(),
+            (19.0, 13.0, 30.5)
+        );
+        assert_eq!(
+            parse_all(key_value("Register A", int::<u64>), "Register A: 729").unwrap(),
+            ("Register A", 729)
+        );
+        assert_eq!(
+            parse_all(key_value(alpha1, space_list(alpha1)), "jqt: rhn xhk").unwrap(),
+            ("jqt", vec!["rhn", "xhk"])
+        );
+    }
+
+    #[test]
+    fn sections_and_grids() {
+        let input = "#.\n.#\n\n1,2\n3,4\r\n\r\n\nlast\n";
+        assert_eq!(sections(input), ["#.\n.#", "1,2\n3,4", "last"]);
+        assert!(sections("\n\n").is_empty());
+        let (grid, _, points) = parse_all((grid, "\n\n", line_list(point2::<u8>)), "#.\n.#\n\n1,2\n3,4").unwrap();
+        assert_eq!((grid.rows, grid.cols), (2, 2));
+        assert_eq!(points, [Point2::new(1, 2), Point2::new(3, 4)]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = parse_all((point2::<i32>, "\n", point2::<i32>), "1,2\n3,x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Custom Error: Parse error at line 2, column 3: unexpected input\n3,x\n  ^"
+        );
+        let error = parse_lines(point2::<i32>, "1,2\n3,4\n5;6").unwrap_err();
+        assert!(error.to_string().contains("line 3, column 2"));
+        assert_eq!(parse_lines(point2::<i32>, "1,2\n3,4").unwrap().len(), 2);
+    }
+}
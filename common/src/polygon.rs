@@ -0,0 +1,128 @@
+// Area and lattice point counts of polygons given by their corners, Ex: a loop dug out on a grid
+
+use num::Integer;
+
+use crate::{Dir4, Point2};
+
+/// Corners visited following the moves from `start`, Ex: `R 6` then `D 5` is `(Dir4::Right, 6), (Dir4::Down, 5)`
+pub fn vertices_from_moves<I>(start: Point2<i64>, moves: I) -> Vec<Point2<i64>>
+where
+    I: IntoIterator<Item = (Dir4, i64)>,
+{
+    moves
+        .into_iter()
+        .scan(start, |position, (dir, steps)| {
+            *position = position.step_n(dir, steps);
+            Some(*position)
+        })
+        .collect()
+}
+
+/// Edges between each corner and the next, wrapping back to the first
+fn edges(vertices: &[Point2<i64>]) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+    vertices.iter().copied().zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the signed area from the shoelace formula, positive when the corners go clockwise on a grid where y grows
+/// going down. Doubled so it stays a whole number
+pub fn double_signed_area(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+}
+
+/// Area inside the edges, half of a whole number for lattice corners so it is returned doubled
+pub fn double_area(vertices: &[Point2<i64>]) -> i64 {
+    double_signed_area(vertices).abs()
+}
+
+/// Lattice points on the edges, the corners included
+pub fn boundary_points(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| (b.x - a.x).gcd(&(b.y - a.y))).sum()
+}
+
+/// Lattice points strictly inside from Pick's theorem, `A = I + B / 2 - 1`
+pub fn interior_points(vertices: &[Point2<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Lattice points inside or on the edges, Ex: the tiles of a dug out loop
+pub fn lattice_points(vertices: &[Point2<i64>]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Whether the point is strictly inside, points on an edge are not
+pub fn point_in_polygon(vertices: &[Point2<i64>], point: Point2<i64>) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        let (ab, ap) = (b - a, point - a);
+        let on_line = ab.x * ap.y == ab.y * ap.x;
+        if on_line
+            && point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y)
+        {
+            return false;
+        }
+        // Cast a ray to the right and count the edges it crosses, each edge covers [low y, high y) so corners
+        // are counted once
+        if (a.y > point.y) != (b.y > point.y) {
+            // Which side of the edge the point is on, flipped for edges going up
+            let side = ab.x * ap.y - ab.y * ap.x;
+            if (side > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dug_loop() {
+        // 2023 day 18 example
+        let moves = "R 6,D 5,L 2,D 2,R 2,D 2,L 5,U 2,L 1,U 2,R 2,U 3,L 2,U 2";
+        let moves = moves.split(',').map(|m| {
+            let (dir, steps) = m.split_once(' ').unwrap();
+            let dir = match dir {
+                "U" => Dir4::Up,
+                "R" => Dir4::Right,
+                "D" => Dir4::Down,
+                _ => Dir4::Left,
+            };
+            (dir, steps.parse().unwrap())
+        });
+        let vertices = vertices_from_moves(Point2::new(0, 0), moves);
+        assert_eq!(vertices.last(), Some(&Point2::new(0, 0)));
+        assert_eq!(boundary_points(&vertices), 38);
+        assert_eq!(double_area(&vertices), 84);
+        assert_eq!(double_signed_area(&vertices), 84);
+        assert_eq!(lattice_points(&vertices), 62);
+        assert_eq!(interior_points(&vertices), 24);
+        assert!(point_in_polygon(&vertices, Point2::new(1, 1)));
+        assert!(!point_in_polygon(&vertices, Point2::new(0, 0)));
+        assert!(!point_in_polygon(&vertices, Point2::new(3, 0)));
+        assert!(!point_in_polygon(&vertices, Point2::new(6, 6)));
+        assert!(!point_in_polygon(&vertices, Point2::new(-1, 3)));
+        let inside = (-1..8)
+            .flat_map(|x| (-1..11).map(move |y| Point2::new(x, y)))
+            .filter(|&p| point_in_polygon(&vertices, p))
+            .count();
+        assert_eq!(inside as i64, interior_points(&vertices));
+    }
+
+    #[test]
+    fn triangle() {
+        let vertices = [Point2::new(0, 0), Point2::new(4, 0), Point2::new(0, 4)];
+        assert_eq!(double_signed_area(&vertices), 16);
+        let reversed = [vertices[2], vertices[1], vertices[0]];
+        assert_eq!(double_signed_area(&reversed), -16);
+        assert_eq!(boundary_points(&vertices), 12);
+        assert_eq!(interior_points(&vertices), 3);
+        assert!(point_in_polygon(&vertices, Point2::new(1, 1)));
+        assert!(!point_in_polygon(&vertices, Point2::new(2, 2)));
+        assert!(!point_in_polygon(&vertices, Point2::new(3, 3)));
+    }
+}
@@ -0,0 +1,143 @@
+// Examples pulled out of a saved puzzle page so they don't have to be copied into the tests by hand
+
+use std::{fs, path::Path};
+
+use regex::Regex;
+
+use crate::{AdventOfCodeError, R};
+
+/// Examples and expected answers found on a puzzle page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Samples {
+    /// Every `<pre><code>` block in the order they show up
+    pub examples: Vec<String>,
+    /// Last highlighted value (`<code><em>`) in each part's description, which is normally the example's answer
+    pub answers: Vec<String>,
+}
+
+/// Finds the examples and answers in the html of a puzzle page
+pub fn parse_puzzle_html(html: &str) -> Samples {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let example = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    // Answers show up as either <code><em>x</em></code> or <em><code>x</code></em>
+    let answer = Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+    let mut samples = Samples::default();
+    for part in article.captures_iter(html) {
+        let part = &part[1];
+        samples
+            .examples
+            .extend(example.captures_iter(part).map(|c| unescape(&strip_tags(&c[1]))));
+        if let Some(c) = answer.captures_iter(part).last() {
+            let value = c.get(1).or(c.get(2)).unwrap().as_str();
+            samples.answers.push(unescape(&strip_tags(value)));
+        }
+    }
+    samples
+}
+
+fn strip_tags(html: &str) -> String {
+    Regex::new(r"<[^>]*>").unwrap().replace_all(html, "").to_string()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads `puzzle.html` in the day's folder and saves each example to `samples/<n>.txt` (starting at 1) and the answers
+/// to `samples/answers.txt`, one line per part
+pub fn save_samples(day_dir: &Path) -> R<Samples> {
+    let page = day_dir.join("puzzle.html");
+    if !page.is_file() {
+        Err(AdventOfCodeError::new(format!(
+            "{} not found, save the puzzle page there first",
+            page.display()
+        )))?
+    }
+    let samples = parse_puzzle_html(&fs::read_to_string(page)?);
+    let dir = day_dir.join("samples");
+    fs::create_dir_all(&dir)?;
+    for (index, example) in samples.examples.iter().enumerate() {
+        // The last line break is only there to close the <pre> block
+        fs::write(
+            dir.join(format!("{}.txt", index + 1)),
+            example.strip_suffix('\n').unwrap_or(example),
+        )?;
+    }
+    fs::write(dir.join("answers.txt"), samples.answers.join("\n"))?;
+    Ok(samples)
+}
+
+/// Loads a saved example, used by `sample!`
+pub fn read_sample(day_dir: &Path, number: usize) -> String {
+    let path = day_dir.join("samples").join(format!("{}.txt", number));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read sample {}: {}", path.display(), e))
+}
+
+/// Loads the saved answer to a part, used by `sample_answer!`
+pub fn read_sample_answer(day_dir: &Path, part: usize) -> String {
+    let path = day_dir.join("samples").join("answers.txt");
+    let answers = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    answers
+        .lines()
+        .nth(part - 1)
+        .unwrap_or_else(|| panic!("No answer for part {} in {}", part, path.display()))
+        .to_string()
+}
+
+/// Example number n saved by `save_samples` for the current package Ex: `sample!(1)`
+#[macro_export]
+macro_rules! sample {
+    ($n:expr) => {
+        common::read_sample(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), $n)
+    };
+}
+
+/// Answer to the example for a part saved by `save_samples` for the current package Ex: `sample_answer!(1)`
+#[macro_export]
+macro_rules! sample_answer {
+    ($part:expr) => {
+        common::read_sample_answer(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), $part)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>1 &lt; 2
+<em>3</em> &amp; 4
+</code></pre>
+<p>Here, the answer is <code>7</code> then <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>55</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a
+b
+</code></pre>
+<p>In total that is <em><code>281</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn puzzle_page() {
+        let samples = parse_puzzle_html(PAGE);
+        assert_eq!(samples.examples, ["1 < 2\n3 & 4\n", "a\nb\n"]);
+        assert_eq!(samples.answers, ["142", "281"]);
+
+        let day_dir = std::env::temp_dir().join(format!("aoc-samples-{}", std::process::id()));
+        fs::create_dir_all(&day_dir).unwrap();
+        assert!(save_samples(&day_dir).is_err());
+        fs::write(day_dir.join("puzzle.html"), PAGE).unwrap();
+        save_samples(&day_dir).unwrap();
+        assert_eq!(read_sample(&day_dir, 2), "a\nb");
+        assert_eq!(read_sample_answer(&day_dir, 1), "142");
+        assert_eq!(read_sample_answer(&day_dir, 2), "281");
+        fs::remove_dir_all(day_dir).unwrap();
+    }
+}
@@ -0,0 +1,522 @@
+// Shortest path searches over any state, the moves from a state come from a closure. Also the longest path through a
+// small graph
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use num::Zero;
+use petgraph::visit::{EdgeRef, GraphProp, IntoEdgeReferences, NodeIndexable};
+use rayon::prelude::*;
+
+/// Path found to a goal, `states` runs from the start to the goal and includes both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPath<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+/// Every state seen so far with the cheapest cost to reach it
+struct Explored<S, C> {
+    indices: HashMap<S, usize>,
+    states: Vec<S>,
+    costs: Vec<C>,
+    /// States the cheapest cost came from, only the first is kept unless ties are wanted
+    parents: Vec<Vec<usize>>,
+}
+
+impl<S, C> Explored<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord,
+{
+    fn new() -> Self {
+        Self {
+            indices: HashMap::new(),
+            states: vec![],
+            costs: vec![],
+            parents: vec![],
+        }
+    }
+
+    /// Records reaching `state` for `cost`, returns its index when it needs to be (re)visited
+    fn insert(&mut self, state: S, cost: C, parent: Option<usize>, keep_ties: bool) -> Option<usize> {
+        match self.indices.get(&state) {
+            Some(&index) if cost < self.costs[index] => {
+                self.costs[index] = cost;
+                self.parents[index] = parent.into_iter().collect();
+                Some(index)
+            }
+            Some(&index) => {
+                if keep_ties && cost == self.costs[index] {
+                    self.parents[index].extend(parent);
+                }
+                None
+            }
+            None => {
+                let index = self.states.len();
+                self.indices.insert(state.clone(), index);
+                self.states.push(state);
+                self.costs.push(cost);
+                self.parents.push(parent.into_iter().collect());
+                Some(index)
+            }
+        }
+    }
+
+    fn path(&self, mut index: usize) -> SearchPath<S, C> {
+        let cost = self.costs[index];
+        let mut states = vec![self.states[index].clone()];
+        while let Some(&parent) = self.parents[index].first() {
+            states.push(self.states[parent].clone());
+            index = parent;
+        }
+        states.reverse();
+        SearchPath { cost, states }
+    }
+
+    /// Every path through the tied parents to the state
+    fn all_paths(&self, index: usize) -> Vec<Vec<S>> {
+        if self.parents[index].is_empty() {
+            return vec![vec![self.states[index].clone()]];
+        }
+        self.parents[index]
+            .iter()
+            .flat_map(|&parent| self.all_paths(parent))
+            .map(|mut path| {
+                path.push(self.states[index].clone());
+                path
+            })
+            .collect()
+    }
+
+    fn into_distances(self) -> HashMap<S, C> {
+        self.states.into_iter().zip(self.costs).collect()
+    }
+}
+
+/// Best first search shared by Dijkstra and A*, returns what it explored and the goals it reached.
+///
+/// With `keep_ties` every goal at the lowest cost is returned and parents are kept for each tied path
+fn best_first<S, C, I, N, IN, H, G>(
+    starts: I,
+    mut successors: N,
+    mut heuristic: H,
+    mut goal: G,
+    keep_ties: bool,
+) -> (Explored<S, C>, Vec<usize>)
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut explored = Explored::new();
+    // Lowest estimated total cost at the top
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Some(index) = explored.insert(start, C::zero(), None, false) {
+            queue.push(Reverse((heuristic(&explored.states[index]), C::zero(), index)));
+        }
+    }
+    let mut best = None;
+    let mut goals = vec![];
+    while let Some(Reverse((estimate, cost, index))) = queue.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        // Already reached for less
+        if cost > explored.costs[index] {
+            continue;
+        }
+        if goal(&explored.states[index]) {
+            best = Some(cost);
+            goals.push(index);
+            if !keep_ties {
+                break;
+            }
+            continue;
+        }
+        let state = explored.states[index].clone();
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if let Some(next) = explored.insert(next, next_cost, Some(index), keep_ties) {
+                queue.push(Reverse((
+                    next_cost + heuristic(&explored.states[next]),
+                    next_cost,
+                    next,
+                )));
+            }
+        }
+    }
+    (explored, goals)
+}
+
+fn breadth_first<S, I, N, IN, G>(starts: I, mut successors: N, mut goal: G) -> (Explored<S, usize>, Option<usize>)
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut explored = Explored::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        queue.extend(explored.insert(start, 0, None, false));
+    }
+    while let Some(index) = queue.pop_front() {
+        if goal(&explored.states[index]) {
+            return (explored, Some(index));
+        }
+        let state = explored.states[index].clone();
+        let cost = explored.costs[index] + 1;
+        for next in successors(&state) {
+            // The first time a state is seen is always the shortest
+            queue.extend(explored.insert(next, cost, Some(index), false));
+        }
+    }
+    (explored, None)
+}
+
+/// Fewest moves from any of the starts to a state matching `goal`, every move costs 1
+pub fn bfs<S, I, N, IN, G>(starts: I, successors: N, goal: G) -> Option<SearchPath<S, usize>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let (explored, goal) = breadth_first(starts, successors, goal);
+    goal.map(|goal| explored.path(goal))
+}
+
+/// Fewest moves from any of the starts to every reachable state
+pub fn bfs_distances<S, I, N, IN>(starts: I, successors: N) -> HashMap<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    breadth_first(starts, successors, |_| false).0.into_distances()
+}
+
+/// Cheapest path from any of the starts to a state matching `goal`, `successors` gives each next state with the cost
+/// to move there
+pub fn dijkstra<S, C, I, N, IN, G>(starts: I, successors: N, goal: G) -> Option<SearchPath<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| C::zero(), goal)
+}
+
+/// Cheapest cost from any of the starts to every reachable state
+pub fn dijkstra_distances<S, C, I, N, IN>(starts: I, successors: N) -> HashMap<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+{
+    best_first(starts, successors, |_| C::zero(), |_| false, false)
+        .0
+        .into_distances()
+}
+
+/// Dijkstra guided by `heuristic`, an estimate of the cost left to a goal.
+///
+/// The estimate must never be more than the real cost, Ex: manhattan distance on a grid
+pub fn astar<S, C, I, N, IN, H, G>(starts: I, successors: N, heuristic: H, goal: G) -> Option<SearchPath<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let (explored, goals) = best_first(starts, successors, heuristic, goal, false);
+    goals.first().map(|&goal| explored.path(goal))
+}
+
+/// Every path tied for the cheapest cost to a goal along with the cost, None when no goal can be reached
+pub fn all_shortest_paths<S, C, I, N, IN, G>(starts: I, successors: N, goal: G) -> Option<(C, Vec<Vec<S>>)>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    let (explored, goals) = best_first(starts, successors, |_| C::zero(), goal, true);
+    let cost = explored.costs[*goals.first()?];
+    Some((
+        cost,
+        goals.into_iter().flat_map(|goal| explored.all_paths(goal)).collect(),
+    ))
+}
+
+/// Path so far in a longest path search, the nodes along it, the visited set and the length
+type Partial = (Vec<usize>, u64, usize);
+
+/// Graph of up to 64 nodes with the visited nodes kept as bits of a `u64`
+struct SimplePaths {
+    /// Nodes each node leads to with the length of the edge
+    edges: Vec<Vec<(usize, usize)>>,
+    /// Same as `edges` as a bitset
+    neighbors: Vec<u64>,
+    /// Longest edge into each node, the most that stepping onto it can add
+    longest_in: Vec<usize>,
+    end: usize,
+}
+
+impl SimplePaths {
+    /// Most that can still be added to a path at `node`, None when the end can't be reached without revisiting
+    fn bound(&self, node: usize, visited: u64) -> Option<usize> {
+        let mut reachable = 0;
+        let mut frontier = self.neighbors[node] & !visited;
+        while frontier != 0 {
+            reachable |= frontier;
+            let next = bits(frontier).fold(0, |next, n| next | self.neighbors[n]);
+            frontier = next & !visited & !reachable;
+        }
+        (reachable & 1 << self.end != 0).then(|| bits(reachable).map(|n| self.longest_in[n]).sum())
+    }
+
+    /// Depth first search from the end of `path`, `best` is the longest found by any thread to prune against
+    fn search(
+        &self,
+        path: &mut Vec<usize>,
+        visited: u64,
+        length: usize,
+        best: &AtomicUsize,
+    ) -> Option<(usize, Vec<usize>)> {
+        let node = *path.last()?;
+        if node == self.end {
+            best.fetch_max(length, Ordering::Relaxed);
+            return Some((length, path.clone()));
+        }
+        if length + self.bound(node, visited)? < best.load(Ordering::Relaxed) {
+            return None;
+        }
+        let mut found: Option<(usize, Vec<usize>)> = None;
+        for &(next, weight) in &self.edges[node] {
+            if visited & 1 << next != 0 {
+                continue;
+            }
+            path.push(next);
+            if let Some(longer) = self.search(path, visited | 1 << next, length + weight, best) {
+                if found.as_ref().is_none_or(|(l, _)| longer.0 > *l) {
+                    found = Some(longer);
+                }
+            }
+            path.pop();
+        }
+        found
+    }
+
+    /// Paths from the start a few steps long so there is one for each thread to search from
+    fn partials(&self, start: usize, wanted: usize) -> Vec<Partial> {
+        let mut partials = vec![(vec![start], 1 << start, 0)];
+        while partials.len() < wanted {
+            let mut next = vec![];
+            let mut grew = false;
+            for (path, visited, length) in partials {
+                let node = path[path.len() - 1];
+                if node == self.end {
+                    next.push((path, visited, length));
+                    continue;
+                }
+                for &(to, weight) in self.edges[node].iter().filter(|(to, _)| visited & 1 << to == 0) {
+                    let mut path = path.clone();
+                    path.push(to);
+                    next.push((path, visited | 1 << to, length + weight));
+                    grew = true;
+                }
+            }
+            partials = next;
+            if !grew {
+                break;
+            }
+        }
+        partials
+    }
+}
+
+/// Indices of the set bits
+fn bits(mut set: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        let bit = (set != 0).then(|| set.trailing_zeros() as usize)?;
+        set &= set - 1;
+        Some(bit)
+    })
+}
+
+/// Longest path from `start` to `end` that visits each node at most once, Ex: the longest hike through the junctions
+/// of a maze. Panics for graphs of more than 64 nodes.
+///
+/// Branches that can't beat the best path so far, even taking the longest edge into every node still reachable, are
+/// cut short. With `parallel` the first few steps are split across threads
+pub fn longest_path<G>(
+    graph: G,
+    start: G::NodeId,
+    end: G::NodeId,
+    parallel: bool,
+) -> Option<SearchPath<G::NodeId, usize>>
+where
+    G: IntoEdgeReferences<EdgeWeight = usize> + NodeIndexable + GraphProp,
+{
+    let nodes = graph.node_bound();
+    assert!(
+        nodes <= 64,
+        "Longest path needs 64 nodes or fewer, the graph has {nodes}"
+    );
+    let mut paths = SimplePaths {
+        edges: vec![vec![]; nodes],
+        neighbors: vec![0; nodes],
+        longest_in: vec![0; nodes],
+        end: graph.to_index(end),
+    };
+    for edge in graph.edge_references() {
+        let (from, to) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        let mut add = |from: usize, to: usize| {
+            paths.edges[from].push((to, *edge.weight()));
+            paths.neighbors[from] |= 1 << to;
+            paths.longest_in[to] = paths.longest_in[to].max(*edge.weight());
+        };
+        add(from, to);
+        if !graph.is_directed() {
+            add(to, from);
+        }
+    }
+
+    let best = AtomicUsize::new(0);
+    let start = graph.to_index(start);
+    let search = |(mut path, visited, length): Partial| paths.search(&mut path, visited, length, &best);
+    let found = if parallel {
+        paths
+            .partials(start, rayon::current_num_threads() * 8)
+            .into_par_iter()
+            .filter_map(search)
+            .collect::<Vec<_>>()
+    } else {
+        search((vec![start], 1 << start, 0)).into_iter().collect()
+    };
+    let (cost, path) = found.into_iter().max_by_key(|(length, _)| *length)?;
+    Some(SearchPath {
+        cost,
+        states: path.into_iter().map(|node| graph.from_index(node)).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dir4, Grid, Point2};
+    use itertools::Itertools;
+
+    const MAZE: &str = "S..#\n.#..\n...E";
+
+    fn open_neighbors(grid: &Grid, point: Point2<isize>) -> Vec<Point2<isize>> {
+        point
+            .neighbors4()
+            .filter(|&p| grid.at(p).is_some_and(|&c| c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn grid_searches() {
+        let grid = Grid::new(MAZE);
+        let start = Point2::new(0, 0);
+        let end = Point2::new(3, 2);
+        let path = bfs([start], |&p| open_neighbors(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!(path.states.first(), Some(&start));
+        assert_eq!(path.states.last(), Some(&end));
+        assert!(path.states.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+
+        let weighted = |&p: &Point2<isize>| open_neighbors(&grid, p).into_iter().map(|p| (p, 1 + p.y as usize));
+        let cheapest = dijkstra([start], weighted, |&p| p == end).unwrap();
+        // Staying on the top row as long as possible is cheapest
+        assert_eq!(cheapest.cost, 9);
+        let guided = astar([start], weighted, |p| p.manhattan(&end) as usize, |&p| p == end).unwrap();
+        assert_eq!(guided.cost, cheapest.cost);
+
+        let distances = bfs_distances([end], |&p| open_neighbors(&grid, p));
+        assert_eq!(distances[&start], 5);
+        assert_eq!(distances.len(), 10);
+        assert_eq!(dijkstra_distances([end], weighted)[&end], 0);
+
+        // Two starts, the one nearer the goal wins
+        let path = bfs([start, Point2::new(2, 1)], |&p| open_neighbors(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(bfs([start], |&p| open_neighbors(&grid, p), |p| p.x > 10), None);
+    }
+
+    #[test]
+    fn tied_paths() {
+        let grid = Grid::new("...\n...");
+        let (cost, paths) = all_shortest_paths(
+            [Point2::new(0, 0)],
+            |&p| {
+                [Dir4::Right, Dir4::Down]
+                    .map(|dir| p.step(dir))
+                    .into_iter()
+                    .filter(|&p| grid.at(p).is_some())
+                    .map(|p| (p, 1))
+            },
+            |&p| p == Point2::new(2, 1),
+        )
+        .unwrap();
+        assert_eq!(cost, 3);
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|path| path.len() == 4));
+        assert_eq!(paths.iter().unique().count(), 3);
+    }
+
+    #[test]
+    fn longest_simple_path() {
+        // 4x4 lattice, the corners are the same colour on a chessboard so one node has to be skipped
+        let mut graph = petgraph::graph::UnGraph::<(), usize>::default();
+        let nodes = (0..16).map(|_| graph.add_node(())).collect_vec();
+        for (row, col) in (0..4).cartesian_product(0..4) {
+            if col < 3 {
+                graph.add_edge(nodes[row * 4 + col], nodes[row * 4 + col + 1], 1);
+            }
+            if row < 3 {
+                graph.add_edge(nodes[row * 4 + col], nodes[row * 4 + col + 4], 1);
+            }
+        }
+        for parallel in [false, true] {
+            let path = longest_path(&graph, nodes[0], nodes[15], parallel).unwrap();
+            assert_eq!(path.cost, 14);
+            assert_eq!(path.states.len(), 15);
+            assert_eq!(path.states.iter().unique().count(), 15);
+            assert!(path.states.windows(2).all(|w| graph.contains_edge(w[0], w[1])));
+        }
+
+        // One way edges, the long way round can't get back
+        let graph = petgraph::graph::DiGraph::<(), usize>::from_edges([(0, 1, 1), (1, 2, 1), (0, 2, 5), (2, 3, 1)]);
+        let path = longest_path(&graph, 0.into(), 3.into(), false).unwrap();
+        assert_eq!((path.cost, path.states.len()), (6, 3));
+        assert_eq!(longest_path(&graph, 3.into(), 0.into(), true), None);
+    }
+}
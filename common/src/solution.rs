@@ -0,0 +1,174 @@
+// Trait implemented by each day so every solution can be run the same way
+
+use std::{env, fmt::Display};
+
+use crate::{bench, take_memo_stats, AdventOfCodeError, Timer, R};
+
+/// A day's solution, the input is parsed once and then handed to both parts
+pub trait Solution {
+    /// Input shared by both parts, use `&'a str` when the parts do their own parsing
+    type Input<'a>;
+    /// Answer to part 1, answers that span multiple lines (Ex: ascii art) are printed on their own lines
+    type Part1: Display;
+    /// Answer to part 2
+    type Part2: Display;
+
+    /// Parses the text input, return the input as is to skip parsing
+    fn parse(input: &str) -> R<Self::Input<'_>>;
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1>;
+
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2>;
+
+    /// Parses the text input then solves part 1
+    fn solve_part1(input: &str) -> R<Self::Part1> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    /// Parses the text input then solves part 2
+    fn solve_part2(input: &str) -> R<Self::Part2> {
+        Self::part2(&Self::parse(input)?)
+    }
+}
+
+/// Implements `Solution` for a day whose parts do their own parsing, they are the day's `part1(&str)` and
+/// `part2(&str)` functions returning `R<..>`, Ex: `solution!(Day01, usize, usize);`
+#[macro_export]
+macro_rules! solution {
+    ($day:ident, $part1:ty, $part2:ty) => {
+        struct $day;
+
+        impl $crate::Solution for $day {
+            type Input<'a> = &'a str;
+            type Part1 = $part1;
+            type Part2 = $part2;
+
+            fn parse(input: &str) -> $crate::R<Self::Input<'_>> {
+                Ok(input)
+            }
+
+            fn part1(input: &Self::Input<'_>) -> $crate::R<Self::Part1> {
+                part1(input)
+            }
+
+            fn part2(input: &Self::Input<'_>) -> $crate::R<Self::Part2> {
+                part2(input)
+            }
+        }
+    };
+}
+
+/// Runs a day printing each answer along with how long it took, followed by the stats of any `Memo::named` it used.
+///
+/// Only one part is run when the `AOC_PART` env var is set to 1 or 2.
+/// When `AOC_BENCH` is set to a number each part (including parsing) is timed that many times and the stats are printed
+pub fn run<D>(input: &str) -> R<()>
+where
+    D: Solution,
+{
+    let (run_part1, run_part2) = match env::var("AOC_PART").as_deref() {
+        Err(_) => (true, true),
+        Ok("1") => (true, false),
+        Ok("2") => (false, true),
+        Ok(part) => Err(AdventOfCodeError::new(format!(
+            "AOC_PART should be 1 or 2, found {}",
+            part
+        )))?,
+    };
+    if let Ok(runs) = env::var("AOC_BENCH") {
+        let runs = runs
+            .parse()
+            .map_err(|_| AdventOfCodeError::new(format!("AOC_BENCH should be a number, found {}", runs)))?;
+        return run_bench::<D>(input, runs, run_part1, run_part2);
+    }
+    let input = {
+        let _timer = Timer::new("Parse");
+        D::parse(input)?
+    };
+    print_memo_stats();
+    if run_part1 {
+        {
+            let _timer = Timer::new("Part 1");
+            print_answer(1, D::part1(&input)?);
+        }
+        print_memo_stats();
+    }
+    if run_part2 {
+        {
+            let _timer = Timer::new("Part 2");
+            print_answer(2, D::part2(&input)?);
+        }
+        print_memo_stats();
+    }
+    Ok(())
+}
+
+fn run_bench<D>(input: &str, runs: usize, run_part1: bool, run_part2: bool) -> R<()>
+where
+    D: Solution,
+{
+    // Memo stats are only printed for the first run, the timed runs would repeat them
+    if run_part1 {
+        print_answer(1, D::solve_part1(input)?);
+        print_memo_stats();
+        println!("Bench of Part 1, {}", bench(runs, || D::solve_part1(input)));
+        take_memo_stats();
+    }
+    if run_part2 {
+        print_answer(2, D::solve_part2(input)?);
+        print_memo_stats();
+        println!("Bench of Part 2, {}", bench(runs, || D::solve_part2(input)));
+        take_memo_stats();
+    }
+    Ok(())
+}
+
+fn print_memo_stats() {
+    for (name, stats) in take_memo_stats() {
+        println!("Memo of {}, {}", name, stats);
+    }
+}
+
+fn print_answer<T>(part: usize, answer: T)
+where
+    T: Display,
+{
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Part{}:\n{}", part, answer);
+    } else {
+        println!("Part{}: {}", part, answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input<'a> = Vec<usize>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> R<Self::Input<'_>> {
+            Ok(input.lines().map(|x| x.parse()).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+            Ok(input.iter().map(|x| "#".repeat(*x)).collect::<Vec<_>>().join("\n"))
+        }
+    }
+
+    #[test]
+    fn solve_parts() {
+        assert_eq!(Sum::solve_part1("1\n2\n3").unwrap(), 6);
+        assert_eq!(Sum::solve_part2("1\n2").unwrap(), "#\n##");
+        assert!(Sum::solve_part1("1\nx").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+// Submitting answers to the site, every answer is logged so known bad answers are never sent twice
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{AdventOfCodeError, HttpProvider, InputStore, R};
+
+/// Which way a wrong answer was off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// The site sometimes says which way the answer was off, and how long to wait before trying again
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently, this one was not checked
+    Wait(Duration),
+    /// The part was already solved, or part 1 is not solved yet
+    WrongLevel,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "Correct"),
+            Outcome::Incorrect { hint, wait } => {
+                write!(f, "Incorrect")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", too high")?,
+                    Some(Hint::TooLow) => write!(f, ", too low")?,
+                    None => (),
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", wait {:?} before trying again", wait)?;
+                }
+                Ok(())
+            }
+            Outcome::Wait(wait) => write!(f, "Answered too recently, wait {:?} before trying again", wait),
+            Outcome::WrongLevel => write!(f, "Wrong level, the part is already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Reads the outcome from the page the site returns after submitting
+pub fn parse_response(body: &str) -> R<Outcome> {
+    if body.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if body.contains("That's not the right answer") {
+        let hint = if body.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if body.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        // Ex: "please wait one minute before trying again", "Please wait 5 minutes before trying again"
+        let minutes = Regex::new(r"(?i)wait (one|\d+) minutes?").unwrap();
+        let wait = minutes.captures(body).map(|c| match &c[1] {
+            "one" => Duration::from_secs(60),
+            n => Duration::from_secs(n.parse::<u64>().unwrap() * 60),
+        });
+        Ok(Outcome::Incorrect { hint, wait })
+    } else if body.contains("You gave an answer too recently") {
+        // Ex: "You have 34s left to wait", "You have 1m 5s left to wait"
+        let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = left
+            .captures(body)
+            .map(|c| {
+                let minutes = c.get(1).map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+                Duration::from_secs(minutes * 60 + c[2].parse::<u64>().unwrap())
+            })
+            .unwrap_or(Duration::from_secs(60));
+        Ok(Outcome::Wait(wait))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::WrongLevel)
+    } else if body.contains("Please log in") || body.contains("[Log In]") {
+        Err(AdventOfCodeError::NotLoggedIn)?
+    } else {
+        Err(AdventOfCodeError::new(
+            "Did not recognize the response to the submitted answer",
+        ))?
+    }
+}
+
+impl HttpProvider {
+    /// Posts an answer to the site, nothing is checked against the log, use `submit_answer` for that
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> R<Outcome> {
+        let session = self.session.as_ref().ok_or(AdventOfCodeError::MissingSession)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let res = self
+            .client
+            .post(&url)
+            .header("Cookie", format!("session={}", session))
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()?;
+        let status = res.status().as_u16();
+        let body = res.text()?;
+        if status != 200 {
+            Err(AdventOfCodeError::HttpStatus { status, url })?
+        }
+        parse_response(&body)
+    }
+}
+
+/// Every answer submitted for one part
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PartLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<String>,
+    /// Lowest answer that was too high
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_high: Option<String>,
+    /// Highest answer that was too low
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub too_low: Option<String>,
+}
+
+/// Answers submitted for a day, saved in the input store next to the day's input
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubmissionLog {
+    /// Seconds since the unix epoch before which the site will not take another answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+    pub part1: PartLog,
+    pub part2: PartLog,
+}
+
+impl SubmissionLog {
+    /// Loads the log, a missing file has nothing submitted
+    pub fn load<P>(path: P) -> R<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if path.is_file() {
+            Ok(toml::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save<P>(&self, path: P) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn part(&self, part: u8) -> &PartLog {
+        match part {
+            1 => &self.part1,
+            2 => &self.part2,
+            _ => panic!("There are only 2 parts, got part {}", part),
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut PartLog {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("There are only 2 parts, got part {}", part),
+        }
+    }
+
+    /// Errors with `NotSubmitted` when the log already shows the answer can't be right, or the site would make us wait
+    pub fn check(&self, part: u8, answer: &str, now: SystemTime) -> R<()> {
+        let log = self.part(part);
+        let reason = if answer.is_empty() {
+            Some("the answer is empty".to_string())
+        } else if let Some(correct) = &log.correct {
+            Some(format!("part {} was already solved with {}", part, correct))
+        } else if log.rejected.iter().any(|x| x == answer) {
+            Some(format!("{} was already rejected", answer))
+        } else if let Some(high) = log
+            .too_high
+            .as_ref()
+            .filter(|high| compare(answer, high).is_some_and(Ordering::is_ge))
+        {
+            Some(format!("{} was too high so {} is too", high, answer))
+        } else if let Some(low) = log
+            .too_low
+            .as_ref()
+            .filter(|low| compare(answer, low).is_some_and(Ordering::is_le))
+        {
+            Some(format!("{} was too low so {} is too", low, answer))
+        } else {
+            self.wait_until
+                .filter(|&until| until > unix_seconds(now))
+                .map(|until| format!("the site needs {}s before another answer", until - unix_seconds(now)))
+        };
+        match reason {
+            Some(reason) => Err(AdventOfCodeError::NotSubmitted(reason))?,
+            None => Ok(()),
+        }
+    }
+
+    /// Saves what the site said about an answer
+    pub fn record(&mut self, part: u8, answer: &str, outcome: &Outcome, now: SystemTime) {
+        let wait = match outcome {
+            Outcome::Correct => {
+                self.part_mut(part).correct = Some(answer.to_string());
+                None
+            }
+            Outcome::Incorrect { hint, wait } => {
+                let log = self.part_mut(part);
+                log.rejected.push(answer.to_string());
+                // Only keep the tightest bounds
+                match hint {
+                    Some(Hint::TooHigh)
+                        if !log
+                            .too_high
+                            .as_ref()
+                            .is_some_and(|x| compare(answer, x).is_some_and(Ordering::is_ge)) =>
+                    {
+                        log.too_high = Some(answer.to_string())
+                    }
+                    Some(Hint::TooLow)
+                        if !log
+                            .too_low
+                            .as_ref()
+                            .is_some_and(|x| compare(answer, x).is_some_and(Ordering::is_le)) =>
+                    {
+                        log.too_low = Some(answer.to_string())
+                    }
+                    _ => (),
+                }
+                *wait
+            }
+            Outcome::Wait(wait) => Some(*wait),
+            Outcome::WrongLevel => None,
+        };
+        self.wait_until = wait.map(|wait| unix_seconds(now) + wait.as_secs());
+    }
+}
+
+/// Compares answers as numbers, None when either is not a number
+fn compare(a: &str, b: &str) -> Option<Ordering> {
+    let a = a.trim().parse::<i128>().ok()?;
+    let b = b.trim().parse::<i128>().ok()?;
+    Some(a.cmp(&b))
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |x| x.as_secs())
+}
+
+/// Submits an answer for the store's profile unless the log shows it can't be right, the outcome is added to the log
+pub fn submit_answer(
+    store: &InputStore,
+    provider: &HttpProvider,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> R<Outcome> {
+    let path = store.submissions_path(year, day);
+    let mut log = SubmissionLog::load(&path)?;
+    let answer = answer.trim();
+    log.check(part, answer, SystemTime::now())?;
+    let outcome = provider.submit(year, day, part, answer)?;
+    log.record(part, answer, &outcome, SystemTime::now());
+    log.save(&path)?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::test_util::stub_server;
+
+    #[test]
+    fn responses() {
+        assert_eq!(
+            parse_response("<article><p>That's the right answer!  You are one gold star closer").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the \
+                 full input data; please wait one minute before trying again."
+            )
+            .unwrap(),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_response("That's not the right answer.  Please wait 5 minutes before trying again.").unwrap(),
+            Outcome::Incorrect {
+                hint: None,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.").unwrap(),
+            Outcome::Wait(Duration::from_secs(65))
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level.  Did you already complete it?").unwrap(),
+            Outcome::WrongLevel
+        );
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn log_guards() {
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+        let mut log = SubmissionLog::default();
+        let too_high = Outcome::Incorrect {
+            hint: Some(Hint::TooHigh),
+            wait: Some(Duration::from_secs(60)),
+        };
+        log.record(1, "500", &too_high, now);
+        log.record(
+            1,
+            "50",
+            &Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait: None,
+            },
+            now,
+        );
+        log.record(1, "abc", &Outcome::Incorrect { hint: None, wait: None }, now);
+        assert_eq!(log.part(1).too_high.as_deref(), Some("500"));
+        assert_eq!(log.part(1).too_low.as_deref(), Some("50"));
+        for answer in ["abc", "500", "600", "50", "10", ""] {
+            assert!(log.check(1, answer, now).is_err(), "{}", answer);
+        }
+        assert!(log.check(1, "100", now).is_ok());
+
+        log.record(1, "100", &Outcome::Wait(Duration::from_secs(30)), now);
+        assert!(log.check(1, "200", now + Duration::from_secs(29)).is_err());
+        assert!(log.check(1, "200", now + Duration::from_secs(30)).is_ok());
+
+        log.record(1, "200", &Outcome::Correct, now);
+        assert!(log.check(1, "300", now).is_err());
+        assert!(log.check(2, "300", now).is_ok());
+
+        let round_trip: SubmissionLog = toml::from_str(&toml::to_string(&log).unwrap()).unwrap();
+        assert_eq!(round_trip, log);
+    }
+
+    #[test]
+    fn submit() {
+        let root = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let store = InputStore::new(&root, "alice");
+
+        let (base_url, request) = stub_server("200 OK", "That's not the right answer; your answer is too low.");
+        let provider = HttpProvider::new(Some("abc")).with_base_url(base_url);
+        assert_eq!(
+            submit_answer(&store, &provider, 2023, 7, 2, "42\n").unwrap(),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait: None
+            }
+        );
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/7/answer "));
+        assert!(request.contains("session=abc"));
+        assert!(request.ends_with("level=2&answer=42"));
+
+        // Known bad answers never reach the server, the stub server only answers once
+        let err = submit_answer(&store, &provider, 2023, 7, 2, "41").unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(AdventOfCodeError::NotSubmitted(_))));
+        assert_eq!(
+            SubmissionLog::load(store.submissions_path(2023, 7))
+                .unwrap()
+                .part(2)
+                .rejected,
+            ["42"]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+// Helpers shared by the tests in this crate
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// Serves a single canned response, returns the base url and the request that was received
+pub fn stub_server(status: &'static str, body: &'static str) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let request = read_request(&mut stream);
+        // Tests that do not check the request drop the receiver
+        let _ = tx.send(request);
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+    });
+    (base_url, rx)
+}
+
+/// Reads the headers and then the body if there is one, the body can come in a separate packet
+fn read_request<S>(stream: &mut S) -> String
+where
+    S: Read,
+{
+    let mut request = vec![];
+    let mut buf = [0; 4096];
+    loop {
+        let len = stream.read(&mut buf).unwrap();
+        request.extend_from_slice(&buf[..len]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+            let content_length = headers
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.trim().parse().ok())
+                .unwrap_or(0);
+            if body.len() >= content_length {
+                return text;
+            }
+        }
+        if len == 0 {
+            return text;
+        }
+    }
+}
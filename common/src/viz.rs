@@ -0,0 +1,599 @@
+// Draws grids in the terminal to watch a simulation run, Ex: rocks falling or robots moving into a picture.
+// Frames can also be saved as PNG and GIF images
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufRead, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::{AdventOfCodeError, Grid, R};
+
+/// Escape code that puts the terminal colour back to its default
+const RESET: &str = "\x1b[0m";
+
+/// Colour a cell is drawn with in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// ANSI escape code that sets the text to this colour
+    fn ansi(self) -> String {
+        match self {
+            Color::Black => "\x1b[30m".to_string(),
+            Color::Red => "\x1b[31m".to_string(),
+            Color::Green => "\x1b[32m".to_string(),
+            Color::Yellow => "\x1b[33m".to_string(),
+            Color::Blue => "\x1b[34m".to_string(),
+            Color::Magenta => "\x1b[35m".to_string(),
+            Color::Cyan => "\x1b[36m".to_string(),
+            Color::White => "\x1b[37m".to_string(),
+            Color::Rgb(r, g, b) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+        }
+    }
+
+    /// Red, green and blue used when drawing images, the named colours are xterm's
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0, 0, 0],
+            Color::Red => [205, 0, 0],
+            Color::Green => [0, 205, 0],
+            Color::Yellow => [205, 205, 0],
+            Color::Blue => [0, 0, 238],
+            Color::Magenta => [205, 0, 205],
+            Color::Cyan => [0, 205, 205],
+            Color::White => [229, 229, 229],
+            Color::Rgb(r, g, b) => [r, g, b],
+        }
+    }
+}
+
+/// How frames are turned into images, each cell is a square of `cell_size` pixels.
+///
+/// A cell's colour comes from the frame when it has one, then the colour given for its char. Otherwise `.` and ` `
+/// are the background and every other char the foreground
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    chars: HashMap<char, Color>,
+    background: Color,
+    foreground: Color,
+    cell_size: u32,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            chars: HashMap::new(),
+            background: Color::Rgb(15, 15, 35),
+            foreground: Color::Rgb(204, 204, 204),
+            cell_size: 4,
+        }
+    }
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Colour for every cell with this char, Ex: `o` for sand in yellow
+    pub fn with_char(mut self, c: char, color: Color) -> Self {
+        self.chars.insert(c, color);
+        self
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn with_foreground(mut self, color: Color) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    /// Panics when the size is 0
+    pub fn with_cell_size(mut self, cell_size: u32) -> Self {
+        assert!(cell_size > 0, "Cells should be at least 1 pixel");
+        self.cell_size = cell_size;
+        self
+    }
+
+    fn color(&self, c: char, color: Option<Color>) -> [u8; 3] {
+        let color = color.or_else(|| self.chars.get(&c).copied()).unwrap_or(match c {
+            '.' | ' ' => self.background,
+            _ => self.foreground,
+        });
+        color.rgb()
+    }
+}
+
+/// One picture of a simulation, cells without a colour use the terminal's default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<char>,
+    pub colors: Grid<Option<Color>>,
+    /// Printed above the cells, Ex: the step number
+    pub caption: Option<String>,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<char>) -> Self {
+        let colors = Grid::filled(cells.rows, cells.cols, None);
+        Self {
+            cells,
+            colors,
+            caption: None,
+        }
+    }
+
+    /// Frame of any grid, each cell is drawn as the char `f` gives it
+    pub fn from_grid<T, F>(grid: &Grid<T>, f: F) -> Self
+    where
+        F: FnMut(&T) -> char,
+    {
+        Self::new(grid.map(f))
+    }
+
+    pub fn with_caption<S>(mut self, caption: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Colours each cell based on its char, Ex: `#` in blue and everything else plain
+    pub fn with_colors<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(char) -> Option<Color>,
+    {
+        self.colors = self.cells.map(|&c| f(c));
+        self
+    }
+
+    /// Colours a single cell, Ex: where the guard is standing
+    pub fn set_color(&mut self, row: usize, col: usize, color: Color) {
+        if let Some(cell) = self.colors.get_mut(row, col) {
+            *cell = Some(color);
+        }
+    }
+
+    /// The caption and cells as text, colours are added with ANSI escape codes when `color` is set
+    pub fn render(&self, color: bool) -> String {
+        let mut text = String::new();
+        if let Some(caption) = &self.caption {
+            text.push_str(caption);
+            text.push('\n');
+        }
+        for (row, (cells, colors)) in self.cells.iter_rows().zip(self.colors.iter_rows()).enumerate() {
+            if row > 0 {
+                text.push('\n');
+            }
+            let mut current = None;
+            for (&c, &cell_color) in cells.iter().zip(colors) {
+                if color && cell_color != current {
+                    text.push_str(&cell_color.map_or(RESET.to_string(), Color::ansi));
+                    current = cell_color;
+                }
+                text.push(c);
+            }
+            if current.is_some() {
+                text.push_str(RESET);
+            }
+        }
+        text
+    }
+
+    /// Pixels row by row of an image `cols` by `rows` cells big, the caption isn't drawn and the space past the
+    /// frame's edges is the background
+    fn pixels(&self, palette: &Palette, cols: usize, rows: usize) -> Vec<[u8; 3]> {
+        let cell_size = palette.cell_size as usize;
+        let background = palette.background.rgb();
+        let mut pixels = Vec::with_capacity(cols * rows * cell_size * cell_size);
+        for row in 0..rows {
+            let cells = (0..cols)
+                .map(|col| match (self.cells.get(row, col), self.colors.get(row, col)) {
+                    (Some(&c), Some(&color)) => palette.color(c, color),
+                    _ => background,
+                })
+                .collect::<Vec<_>>();
+            for _ in 0..cell_size {
+                for &color in &cells {
+                    pixels.extend(std::iter::repeat_n(color, cell_size));
+                }
+            }
+        }
+        pixels
+    }
+
+    pub fn save_png<P>(&self, path: P, palette: &Palette) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        let (cols, rows) = (self.cells.cols, self.cells.rows);
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            (cols as u32) * palette.cell_size,
+            (rows as u32) * palette.cell_size,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels(palette, cols, rows).concat())?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// How frames are shown, picked with the `AOC_VISUALIZE` env var or `aoc run --visualize`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visualize {
+    /// Animates the frames in the terminal at the fps, `AOC_FPS` overrides the animation's fps
+    Play,
+    /// Draws the next frame each time enter is pressed, `q` stops
+    Step,
+    /// Writes every frame to a file, a `.gif` is animated, a `.png` is the last frame and anything else is text
+    File(PathBuf),
+}
+
+impl Visualize {
+    /// `play`, `step` or else the path of the file to write the frames to
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "play" => Visualize::Play,
+            "step" => Visualize::Step,
+            path => Visualize::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Mode asked for with `AOC_VISUALIZE`, None when it isn't set
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_VISUALIZE").ok().map(|value| Self::parse(&value))
+    }
+
+    /// Writes to `<name>-part<part>.<ext>` so the part drawn second doesn't overwrite the first, Ex: `sand-part2.gif`
+    pub fn for_part(self, part: usize) -> Self {
+        match self {
+            Visualize::File(path) => {
+                let mut name = path.file_stem().unwrap_or_default().to_os_string();
+                name.push(format!("-part{}", part));
+                if let Some(extension) = path.extension() {
+                    name.push(".");
+                    name.push(extension);
+                }
+                Visualize::File(path.with_file_name(name))
+            }
+            mode => mode,
+        }
+    }
+}
+
+/// Whether frames should be drawn at all, check this first so a day doesn't pay for drawing frames nobody will see
+pub fn visualizing() -> bool {
+    env::var_os("AOC_VISUALIZE").is_some()
+}
+
+/// Frames played back in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    frames: Vec<Frame>,
+    /// Frames per second when played
+    fps: f64,
+    /// Used when saving the frames as images
+    palette: Palette,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            frames: vec![],
+            fps: 10.0,
+            palette: Palette::default(),
+        }
+    }
+}
+
+impl Animation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fps(mut self, fps: f64) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Draws each frame over the last one, waiting between them to keep to the fps
+    pub fn play<W>(&self, out: &mut W) -> R<()>
+    where
+        W: Write,
+    {
+        if self.fps <= 0.0 {
+            Err(AdventOfCodeError::new(format!(
+                "FPS should be above 0, found {}",
+                self.fps
+            )))?
+        }
+        let delay = Duration::from_secs_f64(1.0 / self.fps);
+        for frame in &self.frames {
+            // Clear the screen and move to the top left
+            writeln!(out, "\x1b[2J\x1b[H{}", frame.render(true))?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    /// Draws the next frame each time a line is read from `input`, a line of `q` stops early
+    pub fn step<W, B>(&self, out: &mut W, input: &mut B) -> R<()>
+    where
+        W: Write,
+        B: BufRead,
+    {
+        let total = self.frames.len();
+        for (index, frame) in self.frames.iter().enumerate() {
+            writeln!(
+                out,
+                "\x1b[2J\x1b[H{}\nFrame {}/{}, enter for the next or q to stop",
+                frame.render(true),
+                index + 1,
+                total
+            )?;
+            out.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || line.trim() == "q" {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every frame as plain text with a blank line between them
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            let _ = writeln!(text, "Frame {}", index + 1);
+            text.push_str(&frame.render(false));
+            text.push_str("\n\n");
+        }
+        text
+    }
+
+    pub fn save<P>(&self, path: P) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+
+    /// Animated GIF that loops forever, frames of different sizes are drawn from the top left on the largest size
+    pub fn save_gif<P>(&self, path: P) -> R<()>
+    where
+        P: AsRef<Path>,
+    {
+        let cols = self.frames.iter().map(|f| f.cells.cols).max().unwrap_or(0);
+        let rows = self.frames.iter().map(|f| f.cells.rows).max().unwrap_or(0);
+        let cell_size = self.palette.cell_size as usize;
+        let (Ok(width), Ok(height)) = (u16::try_from(cols * cell_size), u16::try_from(rows * cell_size)) else {
+            Err(AdventOfCodeError::new(format!(
+                "{}x{} cells of {} pixels is too big for a GIF",
+                cols, rows, cell_size
+            )))?
+        };
+        // A GIF has at most 256 colours, frames only use a few so they share one table of every colour used
+        let mut colors = vec![];
+        let mut indexes = HashMap::new();
+        let mut frames = vec![];
+        for frame in &self.frames {
+            let mut pixels = vec![];
+            for color in frame.pixels(&self.palette, cols, rows) {
+                let index = *indexes.entry(color).or_insert_with(|| {
+                    colors.push(color);
+                    colors.len() - 1
+                });
+                pixels.push(
+                    u8::try_from(index).map_err(|_| AdventOfCodeError::new("A GIF can't have over 256 colours"))?,
+                );
+            }
+            frames.push(pixels);
+        }
+        let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &colors.concat())?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        // Delay is in hundredths of a second
+        let delay = (100.0 / self.fps).round().clamp(1.0, u16::MAX as f64) as u16;
+        for pixels in frames {
+            let mut frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+
+    /// `AOC_FPS` when it is set, otherwise the animation's fps
+    fn env_fps(&self) -> R<f64> {
+        match env::var("AOC_FPS") {
+            Ok(fps) => Ok(fps
+                .parse()
+                .map_err(|_| AdventOfCodeError::new(format!("AOC_FPS should be a number, found {}", fps)))?),
+            Err(_) => Ok(self.fps),
+        }
+    }
+
+    /// Shows the frames the way `AOC_VISUALIZE` asks for, does nothing when it isn't set.
+    ///
+    /// Frames are drawn to stderr so the answers printed to stdout can still be read by the runner
+    pub fn show(&self) -> R<()> {
+        self.show_as(Visualize::from_env())
+    }
+
+    /// Shows the frames of one part, for days that draw both parts. Files get the part added to their name
+    pub fn show_part(&self, part: usize) -> R<()> {
+        self.show_as(Visualize::from_env().map(|mode| mode.for_part(part)))
+    }
+
+    fn show_as(&self, mode: Option<Visualize>) -> R<()> {
+        match mode {
+            None => Ok(()),
+            Some(Visualize::Play) => self.clone().with_fps(self.env_fps()?).play(&mut io::stderr()),
+            Some(Visualize::Step) => self.step(&mut io::stderr(), &mut io::stdin().lock()),
+            Some(Visualize::File(path)) => match path.extension().and_then(|e| e.to_str()) {
+                Some("gif") => self.clone().with_fps(self.env_fps()?).save_gif(path),
+                Some("png") => match self.frames.last() {
+                    Some(frame) => frame.save_png(path, &self.palette),
+                    None => Ok(()),
+                },
+                _ => self.save(path),
+            },
+        }
+    }
+}
+
+impl FromIterator<Frame> for Animation {
+    fn from_iter<I: IntoIterator<Item = Frame>>(iter: I) -> Self {
+        Self {
+            frames: iter.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Frame::new(Grid::new("#..\n.##")).with_colors(|c| (c == '#').then_some(Color::Red))
+    }
+
+    #[test]
+    fn render() {
+        let mut frame = frame().with_caption("Step 1");
+        assert_eq!(frame.render(false), "Step 1\n#..\n.##");
+        assert_eq!(frame.render(true), "Step 1\n\x1b[31m#\x1b[0m..\n.\x1b[31m##\x1b[0m");
+        frame.set_color(0, 2, Color::Rgb(1, 2, 3));
+        assert_eq!(
+            frame.render(true).lines().nth(1).unwrap(),
+            "\x1b[31m#\x1b[0m.\x1b[38;2;1;2;3m.\x1b[0m"
+        );
+        let digits = Frame::from_grid(&Grid::<u8>::parse_digits("12\n34").unwrap(), |&d| {
+            if d > 2 {
+                '#'
+            } else {
+                '.'
+            }
+        });
+        assert_eq!(digits.render(true), "..\n##");
+    }
+
+    #[test]
+    fn animation() {
+        let animation = [frame(), frame().with_caption("Last")]
+            .into_iter()
+            .collect::<Animation>();
+        assert_eq!(animation.len(), 2);
+        assert_eq!(animation.to_text(), "Frame 1\n#..\n.##\n\nFrame 2\nLast\n#..\n.##\n\n");
+
+        // Stops after the first frame when q is read
+        let mut out = vec![];
+        animation.step(&mut out, &mut "q\n".as_bytes()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Frame 1/2") && !out.contains("Frame 2/2"));
+
+        let mut out = vec![];
+        animation.clone().with_fps(1000.0).play(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().matches("\x1b[2J").count(), 2);
+        assert!(animation.with_fps(0.0).play(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(Visualize::parse("play"), Visualize::Play);
+        assert_eq!(Visualize::parse("step"), Visualize::Step);
+        assert_eq!(Visualize::parse("frames.txt"), Visualize::File("frames.txt".into()));
+        assert_eq!(
+            Visualize::parse("out/sand.gif").for_part(2),
+            Visualize::File("out/sand-part2.gif".into())
+        );
+        assert_eq!(
+            Visualize::parse("frames").for_part(1),
+            Visualize::File("frames-part1".into())
+        );
+        assert_eq!(Visualize::Play.for_part(1), Visualize::Play);
+    }
+
+    #[test]
+    fn images() {
+        let dir = env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let palette = Palette::new()
+            .with_cell_size(2)
+            .with_background(Color::Black)
+            .with_char('.', Color::Blue);
+
+        let png_path = dir.join("frame.png");
+        frame().save_png(&png_path, &palette).unwrap();
+        let mut reader = png::Decoder::new(File::open(&png_path).unwrap()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        // Red `#` from the frame then blue `.` from the palette, each 2 pixels wide
+        assert_eq!(pixels[..12], [205, 0, 0, 205, 0, 0, 0, 0, 238, 0, 0, 238]);
+
+        let gif_path = dir.join("frames.gif");
+        let small = Frame::new(Grid::new("#"));
+        let animation = [frame(), small]
+            .into_iter()
+            .collect::<Animation>()
+            .with_palette(palette);
+        animation.with_fps(4.0).save_gif(&gif_path).unwrap();
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(&gif_path).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 4));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 25);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,35 +1,40 @@
 use common::*;
 
-fn main() {
-    let input = read_input_file_for_project_as_string!();
-    {
-        let _timer = Timer::new("Part 1");
-        println!("Part1: {}", part1(&input).unwrap());
+struct {{project-name | pascal_case}};
+
+impl Solution for {{project-name | pascal_case}} {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> R<Self::Input<'_>> {
+        Ok(input)
     }
-    {
-        let _timer = Timer::new("Part 2");
-        println!("Part2: {}", part2(&input).unwrap());
+
+    fn part1(input: &Self::Input<'_>) -> R<Self::Part1> {
+        Err(AdventOfCodeError::UnimplementedError)?
     }
-}
 
-fn part1(input: &str) -> anyhow::Result<usize> {
-    Err(AdventOfCodeError::UnimplementedError)?
+    fn part2(input: &Self::Input<'_>) -> R<Self::Part2> {
+        Err(AdventOfCodeError::UnimplementedError)?
+    }
 }
 
-fn part2(input: &str) -> anyhow::Result<usize> {
-    Err(AdventOfCodeError::UnimplementedError)?
+fn main() -> R<()> {
+    run::<{{project-name | pascal_case}}>(&read_input_file_for_project_as_string!())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     const SAMPLE1: &str = r#""#;
+    answer_tests!({{project-name | pascal_case}});
     #[test]
     fn p1_test() {
-        assert_eq!(part1(SAMPLE1).unwrap(), 0);
+        assert_eq!({{project-name | pascal_case}}::solve_part1(SAMPLE1).unwrap(), 0);
     }
     #[test]
     fn p2_test() {
-        assert_eq!(part2(SAMPLE1).unwrap(), 0);
+        assert_eq!({{project-name | pascal_case}}::solve_part2(SAMPLE1).unwrap(), 0);
     }
 }